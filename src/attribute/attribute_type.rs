@@ -118,6 +118,7 @@ impl AttributeType for AttributeId {
             | AttributeId::GlyphOrientationHorizontal
             | AttributeId::GlyphOrientationVertical
             | AttributeId::ImageRendering
+            | AttributeId::Isolation
             | AttributeId::Kerning
             | AttributeId::LetterSpacing
             | AttributeId::LightingColor
@@ -126,10 +127,14 @@ impl AttributeType for AttributeId {
             | AttributeId::MarkerMid
             | AttributeId::MarkerStart
             | AttributeId::Mask
+            | AttributeId::MixBlendMode
             | AttributeId::Opacity
             | AttributeId::Overflow
+            | AttributeId::PaintOrder
             | AttributeId::PointerEvents
             | AttributeId::ShapeRendering
+            | AttributeId::SolidColor
+            | AttributeId::SolidOpacity
             | AttributeId::StopColor
             | AttributeId::StopOpacity
             | AttributeId::Stroke
@@ -144,6 +149,7 @@ impl AttributeType for AttributeId {
             | AttributeId::TextDecoration
             | AttributeId::TextRendering
             | AttributeId::UnicodeBidi
+            | AttributeId::VectorEffect
             | AttributeId::Visibility
             | AttributeId::WordSpacing
             | AttributeId::WritingMode => true,
@@ -259,14 +265,19 @@ fn is_non_inheritable(id: AttributeId) -> bool {
         | AttributeId::Filter
         | AttributeId::FloodColor
         | AttributeId::FloodOpacity
+        | AttributeId::Isolation
         | AttributeId::LightingColor
         | AttributeId::Mask
+        | AttributeId::MixBlendMode
         | AttributeId::Opacity
         | AttributeId::Overflow
+        | AttributeId::SolidColor
+        | AttributeId::SolidOpacity
         | AttributeId::StopColor
         | AttributeId::StopOpacity
         | AttributeId::TextDecoration
         | AttributeId::UnicodeBidi
+        | AttributeId::VectorEffect
         | AttributeId::Visibility => true,
         _ => false
     }
@@ -6,6 +6,7 @@ use std::fmt;
 
 use {
     AttributeId,
+    FuzzyEq,
     Node,
     ValueId,
     WriteBuffer,
@@ -14,12 +15,21 @@ use {
 };
 use types::{
     path,
+    Angle,
+    AngleList,
+    AngleUnit,
+    AspectRatio,
     Color,
+    IccColor,
     Length,
     LengthList,
     LengthUnit,
     NumberList,
+    PaintOrder,
+    Points,
+    Rect,
     Transform,
+    TransformList,
 };
 
 // TODO: custom debug
@@ -28,7 +38,14 @@ use types::{
 #[derive(Clone,PartialEq,Debug)]
 #[allow(missing_docs)]
 pub enum AttributeValue {
+    Angle(Angle),
+    AngleList(AngleList),
+    AspectRatio(AspectRatio),
     Color(Color),
+    /// The `currentColor` keyword.
+    CurrentColor,
+    /// A color with an ICC color specification.
+    IccColor(IccColor),
     Length(Length),
     LengthList(LengthList),
     /// IRI
@@ -37,10 +54,14 @@ pub enum AttributeValue {
     FuncLink(Node),
     Number(f64),
     NumberList(NumberList),
+    PaintOrder(PaintOrder),
     Path(path::Path),
+    Points(Points),
     PredefValue(ValueId),
     String(String),
     Transform(Transform),
+    TransformList(TransformList),
+    ViewBox(Rect),
 }
 
 macro_rules! impl_from {
@@ -59,9 +80,17 @@ impl_from!(NumberList, NumberList);
 impl_from!(Length, Length);
 impl_from!(LengthList, LengthList);
 impl_from!(Transform, Transform);
+impl_from!(TransformList, TransformList);
 impl_from!(Color, Color);
+impl_from!(IccColor, IccColor);
 impl_from!(ValueId, PredefValue);
 impl_from!(path::Path, Path);
+impl_from!(PaintOrder, PaintOrder);
+impl_from!(AspectRatio, AspectRatio);
+impl_from!(Rect, ViewBox);
+impl_from!(Points, Points);
+impl_from!(Angle, Angle);
+impl_from!(AngleList, AngleList);
 
 // TODO: bad, hidden allocation
 impl<'a> From<&'a str> for AttributeValue {
@@ -105,6 +134,7 @@ impl AttributeValue {
             | AttributeId::GlyphOrientationVertical
             | AttributeId::ImageRendering
             | AttributeId::Kerning
+            | AttributeId::Isolation
             | AttributeId::ShapeRendering
             | AttributeId::TextRendering => some!(ValueId::Auto),
 
@@ -118,23 +148,27 @@ impl AttributeValue {
             | AttributeId::Mask
             | AttributeId::Stroke
             | AttributeId::StrokeDasharray
-            | AttributeId::TextDecoration => some!(ValueId::None),
+            | AttributeId::TextDecoration
+            | AttributeId::VectorEffect => some!(ValueId::None),
 
               AttributeId::FontStretch
             | AttributeId::FontStyle
             | AttributeId::FontVariant
             | AttributeId::FontWeight
             | AttributeId::LetterSpacing
+            | AttributeId::MixBlendMode
             | AttributeId::UnicodeBidi
             | AttributeId::WordSpacing => some!(ValueId::Normal),
 
               AttributeId::Fill
             | AttributeId::FloodColor
+            | AttributeId::SolidColor
             | AttributeId::StopColor => some!(Color::new(0, 0, 0)),
 
               AttributeId::FillOpacity
             | AttributeId::FloodOpacity
             | AttributeId::Opacity
+            | AttributeId::SolidOpacity
             | AttributeId::StopOpacity
             | AttributeId::StrokeOpacity => some!(1.0),
 
@@ -148,8 +182,10 @@ impl AttributeValue {
             AttributeId::Display =>                     some!(ValueId::Inline),
             AttributeId::EnableBackground =>            some!(ValueId::Accumulate),
             AttributeId::FontSize =>                    some!(ValueId::Medium),
-            AttributeId::GlyphOrientationHorizontal =>  some!("0deg"),
+            AttributeId::GlyphOrientationHorizontal =>  some!(Angle::new(0.0, AngleUnit::Degrees)),
             AttributeId::LightingColor =>               some!(Color::new(255, 255, 255)),
+            AttributeId::PaintOrder =>                  some!(PaintOrder::default()),
+            AttributeId::PreserveAspectRatio =>         some!(AspectRatio::default()),
             AttributeId::StrokeDashoffset =>            some!((0.0, LengthUnit::None)),
             AttributeId::StrokeLinecap =>               some!(ValueId::Butt),
             AttributeId::StrokeLinejoin =>              some!(ValueId::Miter),
@@ -165,17 +201,78 @@ impl AttributeValue {
     /// Returns type's name. For the debug purposes.
     pub fn name(&self) -> &str {
         match *self {
+            AttributeValue::Angle(_) => "Angle",
+            AttributeValue::AngleList(_) => "AngleList",
+            AttributeValue::AspectRatio(_) => "AspectRatio",
             AttributeValue::Color(_) => "Color",
+            AttributeValue::CurrentColor => "CurrentColor",
+            AttributeValue::IccColor(_) => "IccColor",
             AttributeValue::Length(_) => "Length",
             AttributeValue::LengthList(_) => "LengthList",
             AttributeValue::Link(_) => "Link",
             AttributeValue::FuncLink(_) => "FuncLink",
             AttributeValue::Number(_) => "Number",
             AttributeValue::NumberList(_) => "NumberList",
+            AttributeValue::PaintOrder(_) => "PaintOrder",
             AttributeValue::Path(_) => "Path",
+            AttributeValue::Points(_) => "Points",
             AttributeValue::PredefValue(_) => "PredefValue",
             AttributeValue::String(_) => "String",
             AttributeValue::Transform(_) => "Transform",
+            AttributeValue::TransformList(_) => "TransformList",
+            AttributeValue::ViewBox(_) => "ViewBox",
+        }
+    }
+
+    /// Compares two values using fuzzy float compare algorithm.
+    ///
+    /// Links are compared by the ID of the referenced element,
+    /// so values from different documents can be compared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a linked node is currently mutably borrowed.
+    pub fn fuzzy_eq(&self, other: &AttributeValue) -> bool {
+        match (self, other) {
+            (&AttributeValue::Number(ref a), &AttributeValue::Number(ref b)) => {
+                a.fuzzy_eq(b)
+            }
+            (&AttributeValue::NumberList(ref a), &AttributeValue::NumberList(ref b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.fuzzy_eq(b))
+            }
+            (&AttributeValue::Length(ref a), &AttributeValue::Length(ref b)) => {
+                a.unit == b.unit && a.num.fuzzy_eq(&b.num)
+            }
+            (&AttributeValue::LengthList(ref a), &AttributeValue::LengthList(ref b)) => {
+                   a.len() == b.len()
+                && a.iter().zip(b).all(|(a, b)| a.unit == b.unit && a.num.fuzzy_eq(&b.num))
+            }
+            (&AttributeValue::Angle(ref a), &AttributeValue::Angle(ref b)) => {
+                a.fuzzy_eq(b)
+            }
+            (&AttributeValue::AngleList(ref a), &AttributeValue::AngleList(ref b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.fuzzy_eq(b))
+            }
+            (&AttributeValue::Transform(ref a), &AttributeValue::Transform(ref b)) => {
+                a.fuzzy_eq(b)
+            }
+            (&AttributeValue::TransformList(ref a), &AttributeValue::TransformList(ref b)) => {
+                a.fuzzy_eq(b)
+            }
+            (&AttributeValue::ViewBox(ref a), &AttributeValue::ViewBox(ref b)) => {
+                a.fuzzy_eq(b)
+            }
+            (&AttributeValue::Points(ref a), &AttributeValue::Points(ref b)) => {
+                a.fuzzy_eq(b)
+            }
+            (&AttributeValue::Path(ref a), &AttributeValue::Path(ref b)) => {
+                a.d.len() == b.d.len() && a.d.iter().zip(&b.d).all(|(a, b)| a.fuzzy_eq(b))
+            }
+              (&AttributeValue::Link(ref a), &AttributeValue::Link(ref b))
+            | (&AttributeValue::FuncLink(ref a), &AttributeValue::FuncLink(ref b)) => {
+                *a.id() == *b.id()
+            }
+            _ => self == other,
         }
     }
 }
@@ -207,9 +304,30 @@ impl WriteBuffer for AttributeValue {
             AttributeValue::Transform(ref t) => {
                 t.write_buf_opt(opt, buf);
             }
+            AttributeValue::TransformList(ref t) => {
+                t.write_buf_opt(opt, buf);
+            }
             AttributeValue::Path(ref p) => {
                 p.write_buf_opt(opt, buf);
             }
+            AttributeValue::PaintOrder(ref p) => {
+                p.write_buf_opt(opt, buf);
+            }
+            AttributeValue::AspectRatio(ref a) => {
+                a.write_buf_opt(opt, buf);
+            }
+            AttributeValue::Angle(ref a) => {
+                a.write_buf_opt(opt, buf);
+            }
+            AttributeValue::AngleList(ref list) => {
+                list.write_buf_opt(opt, buf);
+            }
+            AttributeValue::ViewBox(ref r) => {
+                r.write_buf_opt(opt, buf);
+            }
+            AttributeValue::Points(ref p) => {
+                p.write_buf_opt(opt, buf);
+            }
             AttributeValue::Link(ref n) => {
                 buf.push(b'#');
                 buf.extend_from_slice(n.id().as_bytes());
@@ -222,6 +340,12 @@ impl WriteBuffer for AttributeValue {
             AttributeValue::Color(ref c) => {
                 c.write_buf_opt(opt, buf);
             },
+            AttributeValue::CurrentColor => {
+                buf.extend_from_slice(b"currentColor");
+            },
+            AttributeValue::IccColor(ref c) => {
+                c.write_buf_opt(opt, buf);
+            },
             AttributeValue::PredefValue(ref v) => {
                 buf.extend_from_slice(v.name().as_bytes())
             },
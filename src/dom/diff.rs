@@ -0,0 +1,357 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::cmp;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use {
+    AttributeName,
+    Document,
+    Node,
+    NodeType,
+};
+
+/// A structural difference between two documents.
+///
+/// See [`Document::diff`] for details.
+///
+/// [`Document::diff`]: struct.Document.html#method.diff
+#[derive(Clone,Debug)]
+pub enum Change {
+    /// A node was inserted. Contains a node from the new document.
+    ///
+    /// Only the topmost inserted node is reported. Its children are implied.
+    Inserted(Node),
+    /// A node was removed. Contains a node from the old document.
+    ///
+    /// Only the topmost removed node is reported. Its children are implied.
+    Removed(Node),
+    /// A node was moved to a different parent or to a different position among its siblings.
+    Moved {
+        /// A node from the old document.
+        old: Node,
+        /// A node from the new document.
+        new: Node,
+    },
+    /// An attribute was inserted, removed or changed.
+    Attribute {
+        /// A node from the old document.
+        old: Node,
+        /// A node from the new document.
+        new: Node,
+        /// An attribute name.
+        name: AttributeName,
+    },
+    /// An element ID was changed.
+    Id {
+        /// A node from the old document.
+        old: Node,
+        /// A node from the new document.
+        new: Node,
+    },
+    /// A text data of a non-element node was changed.
+    Text {
+        /// A node from the old document.
+        old: Node,
+        /// A node from the new document.
+        new: Node,
+    },
+}
+
+/// The maximum size of the table used by the children matching.
+///
+/// The greedy algorithm will be used for a larger lists.
+const MAX_TABLE_SIZE: usize = 1_000_000;
+
+/// Matched nodes of two documents.
+pub struct Matching {
+    pub old_to_new: HashMap<Node, Node>,
+    pub new_to_old: HashMap<Node, Node>,
+}
+
+impl Matching {
+    fn insert(&mut self, old: Node, new: Node) {
+        self.old_to_new.insert(old.clone(), new.clone());
+        self.new_to_old.insert(new, old);
+    }
+}
+
+pub fn diff(old: &Document, new: &Document) -> Vec<Change> {
+    let m = match_nodes(old, new);
+    diff_matched(old, new, &m)
+}
+
+/// Matches nodes of two documents.
+pub fn match_nodes(old: &Document, new: &Document) -> Matching {
+    let mut m = Matching {
+        old_to_new: HashMap::new(),
+        new_to_old: HashMap::new(),
+    };
+
+    m.insert(old.root(), new.root());
+    let mut queue = vec![(old.root(), new.root())];
+
+    // Elements with the same ID are always matched.
+    let old_ids = collect_ids(old);
+    let new_ids = collect_ids(new);
+    for (id, old_node) in &old_ids {
+        if let Some(new_node) = new_ids.get(id) {
+            if is_same_kind(old_node, new_node) {
+                m.insert(old_node.clone(), new_node.clone());
+                queue.push((old_node.clone(), new_node.clone()));
+            }
+        }
+    }
+
+    // Other nodes are matched by position.
+    while let Some((old_parent, new_parent)) = queue.pop() {
+        let old_list: Vec<Node> = old_parent.children()
+            .filter(|n| !m.old_to_new.contains_key(n) && !new_ids.contains_key(&*n.id()))
+            .collect();
+
+        let new_list: Vec<Node> = new_parent.children()
+            .filter(|n| !m.new_to_old.contains_key(n) && !old_ids.contains_key(&*n.id()))
+            .collect();
+
+        for (i, j) in match_lists(&old_list, &new_list) {
+            m.insert(old_list[i].clone(), new_list[j].clone());
+            queue.push((old_list[i].clone(), new_list[j].clone()));
+        }
+    }
+
+    m
+}
+
+/// Returns changes between two documents with already matched nodes.
+pub fn diff_matched(old: &Document, new: &Document, m: &Matching) -> Vec<Change> {
+    let reordered = collect_reordered(m);
+
+    let mut changes = Vec::new();
+
+    for old_node in old.descendants().skip(1) {
+        let new_node = match m.old_to_new.get(&old_node) {
+            Some(n) => n.clone(),
+            None => {
+                if is_matched(old_node.parent(), &m.old_to_new) {
+                    changes.push(Change::Removed(old_node));
+                }
+                continue;
+            }
+        };
+
+        let old_parent = old_node.parent().and_then(|p| m.old_to_new.get(&p).cloned());
+        if old_parent != new_node.parent() || reordered.contains(&old_node) {
+            changes.push(Change::Moved { old: old_node.clone(), new: new_node.clone() });
+        }
+
+        if old_node.node_type() == NodeType::Element {
+            if *old_node.id() != *new_node.id() {
+                changes.push(Change::Id { old: old_node.clone(), new: new_node.clone() });
+            }
+
+            diff_attributes(&old_node, &new_node, &mut changes);
+        } else if *old_node.text() != *new_node.text() {
+            changes.push(Change::Text { old: old_node.clone(), new: new_node.clone() });
+        }
+    }
+
+    for new_node in new.descendants().skip(1) {
+        if !m.new_to_old.contains_key(&new_node) && is_matched(new_node.parent(), &m.new_to_old) {
+            changes.push(Change::Inserted(new_node));
+        }
+    }
+
+    changes
+}
+
+fn collect_ids(doc: &Document) -> HashMap<String, Node> {
+    let mut ids = HashMap::new();
+    for node in doc.descendants().filter(|n| n.has_id()) {
+        let id = node.id().clone();
+        ids.entry(id).or_insert(node);
+    }
+
+    ids
+}
+
+pub fn is_same_kind(a: &Node, b: &Node) -> bool {
+    if a.node_type() != b.node_type() {
+        return false;
+    }
+
+    match (a.tag_name(), b.tag_name()) {
+        (Some(a), Some(b)) => *a == *b,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn is_matched(node: Option<Node>, map: &HashMap<Node, Node>) -> bool {
+    match node {
+        Some(node) => map.contains_key(&node),
+        None => false,
+    }
+}
+
+/// Returns pairs of indexes of the matched nodes.
+///
+/// Based on the longest common subsequence of the node kinds.
+fn match_lists(old: &[Node], new: &[Node]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+
+    // Skip the common prefix and suffix to reduce the table size.
+    let mut start = 0;
+    while start < old.len() && start < new.len() && is_same_kind(&old[start], &new[start]) {
+        pairs.push((start, start));
+        start += 1;
+    }
+
+    let mut end = 0;
+    while    end < old.len() - start && end < new.len() - start
+          && is_same_kind(&old[old.len() - 1 - end], &new[new.len() - 1 - end]) {
+        end += 1;
+    }
+
+    let old_mid = &old[start..old.len() - end];
+    let new_mid = &new[start..new.len() - end];
+
+    if (old_mid.len() + 1) * (new_mid.len() + 1) <= MAX_TABLE_SIZE {
+        let w = new_mid.len() + 1;
+        let mut table = vec![0u32; (old_mid.len() + 1) * w];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                table[i * w + j] = if is_same_kind(&old_mid[i], &new_mid[j]) {
+                    table[(i + 1) * w + j + 1] + 1
+                } else {
+                    cmp::max(table[(i + 1) * w + j], table[i * w + j + 1])
+                };
+            }
+        }
+
+        let mut i = 0;
+        let mut j = 0;
+        while i < old_mid.len() && j < new_mid.len() {
+            if is_same_kind(&old_mid[i], &new_mid[j]) {
+                pairs.push((start + i, start + j));
+                i += 1;
+                j += 1;
+            } else if table[(i + 1) * w + j] >= table[i * w + j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    } else {
+        let mut j = 0;
+        for (i, node) in old_mid.iter().enumerate() {
+            if let Some(k) = new_mid[j..].iter().position(|n| is_same_kind(node, n)) {
+                pairs.push((start + i, start + j + k));
+                j += k + 1;
+            }
+        }
+    }
+
+    for k in 0..end {
+        pairs.push((old.len() - end + k, new.len() - end + k));
+    }
+
+    pairs
+}
+
+/// Returns old nodes that changed their position among siblings.
+fn collect_reordered(m: &Matching) -> HashSet<Node> {
+    let mut reordered = HashSet::new();
+
+    for (old_parent, new_parent) in &m.old_to_new {
+        let old_list: HashMap<Node, usize> = old_parent.children()
+            .enumerate()
+            .map(|(i, n)| (n, i))
+            .collect();
+
+        // Old indexes of the nodes that stay in the same parent, in the new order.
+        let mut nodes = Vec::new();
+        let mut indexes = Vec::new();
+        for new_node in new_parent.children() {
+            if let Some(old_node) = m.new_to_old.get(&new_node) {
+                if let Some(idx) = old_list.get(old_node) {
+                    nodes.push(old_node.clone());
+                    indexes.push(*idx);
+                }
+            }
+        }
+
+        let stable = longest_increasing(&indexes);
+        for (i, node) in nodes.into_iter().enumerate() {
+            if !stable.contains(&i) {
+                reordered.insert(node);
+            }
+        }
+    }
+
+    reordered
+}
+
+/// Returns indexes of the longest increasing subsequence.
+fn longest_increasing(list: &[usize]) -> HashSet<usize> {
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev = vec![None; list.len()];
+
+    for i in 0..list.len() {
+        let pos = match tails.binary_search_by(|t| list[*t].cmp(&list[i])) {
+            Ok(pos) | Err(pos) => pos,
+        };
+
+        if pos > 0 {
+            prev[i] = Some(tails[pos - 1]);
+        }
+
+        if pos == tails.len() {
+            tails.push(i);
+        } else {
+            tails[pos] = i;
+        }
+    }
+
+    let mut set = HashSet::new();
+    let mut idx = tails.last().cloned();
+    while let Some(i) = idx {
+        set.insert(i);
+        idx = prev[i];
+    }
+
+    set
+}
+
+fn diff_attributes(old: &Node, new: &Node, changes: &mut Vec<Change>) {
+    let old_attrs = old.attributes();
+    let new_attrs = new.attributes();
+
+    for attr in old_attrs.iter() {
+        let is_changed = match new_attrs.get(attr.name.into_ref()) {
+            Some(new_attr) => !attr.value.fuzzy_eq(&new_attr.value),
+            None => true,
+        };
+
+        if is_changed {
+            changes.push(Change::Attribute {
+                old: old.clone(),
+                new: new.clone(),
+                name: attr.name.clone(),
+            });
+        }
+    }
+
+    for attr in new_attrs.iter() {
+        if !old_attrs.contains(attr.name.into_ref()) {
+            changes.push(Change::Attribute {
+                old: old.clone(),
+                new: new.clone(),
+                name: attr.name.clone(),
+            });
+        }
+    }
+}
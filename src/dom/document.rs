@@ -3,6 +3,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt;
 use std::rc::Rc;
 
@@ -15,15 +16,23 @@ use error::Result;
 use writer;
 use {
     Attributes,
+    AttributeValue,
+    Change,
     Children,
+    Conflict,
     Descendants,
     ElementId,
+    ErrorKind,
+    Mutation,
     NameRef,
     Node,
     NodeType,
+    ObserverId,
+    ReferenceGraph,
     TagName,
     TagNameRef,
     ToStringWithOptions,
+    Violation,
     WriteBuffer,
     WriteOptions,
 };
@@ -31,6 +40,12 @@ use super::node_data::{
     Link,
     NodeData,
 };
+use super::observer::Observers;
+use super::user_data::UserData;
+use super::diff;
+use super::merge;
+use super::transforms;
+use super::validation;
 
 /// Container of [`Node`]s.
 ///
@@ -141,9 +156,9 @@ impl Document {
     ///
     /// [`Node`]: struct.Node.html
     pub fn svg_element(&self) -> Option<Node> {
-        for (id, n) in self.root.children().nth(1).and_then(|n| n.children().svg().nth(0)) {
+        if let Some((id, n)) = self.root.children().svg().next() {
             if id == ElementId::Svg {
-                return Some(n.clone());
+                return Some(n);
             }
         }
 
@@ -171,6 +186,417 @@ impl Document {
         new_child.clone()
     }
 
+    /// Imports a node from another `Document`.
+    ///
+    /// Returns a copy of the `node` that belongs to this document,
+    /// but not added to it tree structure. Unlike [`Node::make_copy`],
+    /// IDs are preserved.
+    ///
+    /// If `deep` is set, all children will be copied too.
+    ///
+    /// All elements referenced by the copied nodes via `Link` and `FuncLink` attributes,
+    /// like gradients, clip paths and filters, are imported too, recursively.
+    /// Such elements are appended to the first `defs` element of this document,
+    /// which will be created when missing.
+    /// Links of the copied nodes are pointed to the copies.
+    ///
+    /// If an ID is already used by this document, then the copied node will be renamed
+    /// using the `{id}_{n}` pattern.
+    ///
+    /// If the `node` belongs to this document, then references are not copied.
+    ///
+    /// # Errors
+    ///
+    /// - [`NoSvgElement`] if referenced elements should be imported,
+    ///   but the document doesn't have an `svg` element.
+    /// - [`ElementCrosslink`] if copied elements are referencing each other.
+    ///
+    /// The document is not changed on error.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `node` is a root node.
+    /// - Panics if any of the processed nodes are currently mutability borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, ElementId};
+    ///
+    /// let doc1 = Document::from_str(
+    /// "<svg>
+    ///     <linearGradient id='lg1'/>
+    ///     <rect fill='url(#lg1)'/>
+    /// </svg>").unwrap();
+    ///
+    /// let mut doc2 = Document::from_str(
+    /// "<svg>
+    ///     <linearGradient id='lg1'/>
+    /// </svg>").unwrap();
+    ///
+    /// let rect = doc1.descendants().find(|n| n.is_tag_name(ElementId::Rect)).unwrap();
+    /// let rect = doc2.import_node(&rect, true).unwrap();
+    /// doc2.svg_element().unwrap().append(&rect);
+    ///
+    /// assert_eq!(doc2.to_string(),
+    /// "<svg>
+    ///     <defs>
+    ///         <linearGradient id=\"lg1_1\"/>
+    ///     </defs>
+    ///     <linearGradient id=\"lg1\"/>
+    ///     <rect fill=\"url(#lg1_1)\"/>
+    /// </svg>
+    /// ");
+    /// ```
+    ///
+    /// [`Node::make_copy`]: struct.Node.html#method.make_copy
+    /// [`NoSvgElement`]: enum.ErrorKind.html
+    /// [`ElementCrosslink`]: enum.ErrorKind.html
+    pub fn import_node(&mut self, node: &Node, deep: bool) -> Result<Node> {
+        assert!(node.node_type() != NodeType::Root, "a root node can't be imported");
+
+        let is_same_doc = node.document().root == self.root;
+
+        let mut ids: HashSet<String> = self.descendants()
+                                           .filter(|n| n.has_id())
+                                           .map(|n| n.id().clone())
+                                           .collect();
+
+        // List of all copied nodes as (original, copy).
+        let mut copies = Vec::new();
+        let new_node = self.import_node_impl(node, deep, &mut ids, &mut copies);
+
+        // Import referenced elements. The `copies` list will grow during iteration,
+        // so nested references will be processed too.
+        let mut refs = Vec::new();
+        if !is_same_doc {
+            let mut i = 0;
+            while i < copies.len() {
+                let orig = copies[i].0.clone();
+                i += 1;
+
+                for link in links_list(&orig) {
+                    if !copies.iter().any(|c| c.0 == link) {
+                        refs.push(self.import_node_impl(&link, true, &mut ids, &mut copies));
+                    }
+                }
+            }
+        }
+
+        // Restore links. Copies are not part of the tree yet,
+        // so the document is not changed on error.
+        for c in &copies {
+            let mut copy = c.1.clone();
+            let attrs = c.0.attributes();
+            for (aid, attr) in attrs.iter_svg() {
+                match attr.value {
+                      AttributeValue::Link(ref link)
+                    | AttributeValue::FuncLink(ref link) => {
+                        let link = match copies.iter().find(|c| c.0 == *link) {
+                            Some(c) => c.1.clone(),
+                            None => link.clone(),
+                        };
+
+                        copy.set_attribute_checked((aid, link))?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if !refs.is_empty() {
+            let mut defs = self.defs_element()?;
+            for n in &refs {
+                defs.append(n);
+            }
+        }
+
+        Ok(new_node)
+    }
+
+    fn import_node_impl(
+        &mut self,
+        node: &Node,
+        deep: bool,
+        ids: &mut HashSet<String>,
+        copies: &mut Vec<(Node, Node)>,
+    ) -> Node {
+        let mut new_node = match node.node_type() {
+            NodeType::Element => {
+                let mut elem = self.create_element(node.tag_name().unwrap().into_ref());
+
+                for attr in node.attributes().iter() {
+                    if !attr.is_link() && !attr.is_func_link() {
+                        elem.set_attribute(attr.clone());
+                    }
+                }
+
+                if node.has_id() {
+                    let mut id = node.id().clone();
+                    if ids.contains(&id) {
+                        id = gen_unique_id(ids, &id);
+                    }
+
+                    ids.insert(id.clone());
+                    elem.set_id(id);
+                }
+
+                elem
+            }
+            _ => {
+                self.create_node(node.node_type(), &node.text())
+            }
+        };
+
+        copies.push((node.clone(), new_node.clone()));
+
+        if deep {
+            for child in node.children() {
+                let new_child = self.import_node_impl(&child, deep, ids, copies);
+                new_node.append(&new_child);
+            }
+        }
+
+        new_node
+    }
+
+    /// Returns the first `defs` element of the `svg` element. Creates a new one when missing.
+    fn defs_element(&mut self) -> Result<Node> {
+        let mut svg = match self.svg_element() {
+            Some(svg) => svg,
+            None => return Err(ErrorKind::NoSvgElement.into()),
+        };
+
+        if let Some(defs) = svg.children().find(|n| n.is_tag_name(ElementId::Defs)) {
+            return Ok(defs);
+        }
+
+        let defs = self.create_element(ElementId::Defs);
+        svg.prepend(&defs);
+        Ok(defs)
+    }
+
+    /// Constructs a [`ReferenceGraph`] of the current document.
+    ///
+    /// [`ReferenceGraph`]: struct.ReferenceGraph.html
+    pub fn reference_graph(&self) -> ReferenceGraph {
+        ReferenceGraph::new(self)
+    }
+
+    /// Returns a structural difference between this and the `new` document.
+    ///
+    /// Nodes are matched in two steps. First, elements with the same ID and tag name
+    /// are matched. Then, remaining nodes are matched by the position and the tag name
+    /// among the children of already matched nodes.
+    ///
+    /// Attribute values are compared semantically using [`AttributeValue::fuzzy_eq`],
+    /// so the numbers formatting and the attributes order are ignored.
+    ///
+    /// Changes are listed in the order of the old document,
+    /// followed by insertions in the order of the new document.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the nodes are currently mutability borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, Change};
+    ///
+    /// let old = Document::from_str("<svg><rect width='10.0'/><circle/></svg>").unwrap();
+    /// let new = Document::from_str("<svg><rect width='10'/></svg>").unwrap();
+    ///
+    /// let changes = old.diff(&new);
+    /// assert_eq!(changes.len(), 1);
+    /// match changes[0] {
+    ///     Change::Removed(ref node) => assert_eq!(node.tag_id(), Some(svgdom::ElementId::Circle)),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    ///
+    /// [`AttributeValue::fuzzy_eq`]: enum.AttributeValue.html#method.fuzzy_eq
+    pub fn diff(&self, new: &Document) -> Vec<Change> {
+        diff::diff(self, new)
+    }
+
+    /// Merges two documents derived from this one.
+    ///
+    /// Returns a new document and a list of conflicts.
+    ///
+    /// The merged document is a copy of `ours` with all non-conflicting changes
+    /// from `theirs` applied. Changes are detected using [`diff`] against this document.
+    ///
+    /// Changes to different attributes of the same element are merged.
+    /// Identical changes on both sides are not conflicts.
+    /// In case of a conflict, the version from `ours` is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the nodes are currently mutability borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::Document;
+    ///
+    /// let base = Document::from_str("<svg><rect x='1' y='1'/></svg>").unwrap();
+    /// let ours = Document::from_str("<svg><rect x='2' y='1'/></svg>").unwrap();
+    /// let theirs = Document::from_str("<svg><rect x='1' y='2'/><circle/></svg>").unwrap();
+    ///
+    /// let (doc, conflicts) = base.merge(&ours, &theirs);
+    /// assert!(conflicts.is_empty());
+    /// assert_eq!(doc.to_string(),
+    /// "<svg>
+    ///     <rect x=\"2\" y=\"2\"/>
+    ///     <circle/>
+    /// </svg>
+    /// ");
+    /// ```
+    ///
+    /// [`diff`]: #method.diff
+    pub fn merge(&self, ours: &Document, theirs: &Document) -> (Document, Vec<Conflict>) {
+        merge::merge(self, ours, theirs)
+    }
+
+    /// Validates the document against the SVG 1.1 content model.
+    ///
+    /// Checks:
+    ///
+    /// - elements nesting and a text data placement
+    /// - required attributes, like `r` on `circle` or `d` on `path`
+    /// - attributes applicability, like presentation attributes on animation elements
+    ///   or `cx` on `rect`
+    /// - attribute value ranges, like a negative `width` or `opacity` outside the 0..1 range
+    ///
+    /// Returns a list of violations in document order.
+    ///
+    /// Validation is not exhaustive. Non-SVG elements and attributes are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, AttributeId, ViolationKind};
+    ///
+    /// let doc = Document::from_str("<svg><circle cx='5' r='-1'/><path/></svg>").unwrap();
+    ///
+    /// let list = doc.validate();
+    /// assert_eq!(list.len(), 2);
+    /// assert_eq!(list[0].kind, ViolationKind::InvalidValue(AttributeId::R));
+    /// assert_eq!(list[1].kind, ViolationKind::MissingAttribute(AttributeId::D));
+    /// ```
+    pub fn validate(&self) -> Vec<Violation> {
+        validation::validate(self)
+    }
+
+    /// Applies group and path transforms to the path data.
+    ///
+    /// First, transforms of `g` elements are moved to their children.
+    /// Then, transforms of `path` elements are applied to their `d` attribute.
+    ///
+    /// A transform is kept when moving or applying it would change the rendering.
+    /// So group transforms are not moved when the group has a `clip-path`, `mask`
+    /// or `filter`, or when it contains animations or non-graphic elements.
+    /// And path transforms are not applied when the path references a paint server,
+    /// a marker, a clipping path, a mask or a filter, has animations
+    /// or has a stroke and the transform contains a scale or a skew.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the nodes are currently mutability borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::Document;
+    ///
+    /// let mut doc = Document::from_str(
+    ///     "<svg><g transform='translate(10 20)'><path d='M 0 0 L 10 10'/></g></svg>").unwrap();
+    /// doc.bake_transforms();
+    /// assert_eq!(doc.to_string(),
+    /// "<svg>
+    ///     <g>
+    ///         <path d=\"M 10 20 L 20 30\"/>
+    ///     </g>
+    /// </svg>
+    /// ");
+    /// ```
+    pub fn bake_transforms(&mut self) {
+        transforms::bake_transforms(self)
+    }
+
+    /// Registers a document observer and returns its ID.
+    ///
+    /// The observer will be called after each modification of any node
+    /// of this document, made by:
+    ///
+    /// - `append`, `prepend`, `insert_before` and `insert_after`
+    /// - `detach` and `remove`
+    /// - `set_attribute` and `remove_attribute`
+    /// - `set_text`
+    /// - `set_id`
+    ///
+    /// Modifications via `attributes_mut` and `text_mut` are not reported.
+    /// Neither are `set_text` and `set_id` calls that don't change the value.
+    ///
+    /// Observers are called in the registration order.
+    /// Modifications made by an observer itself are not reported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from an observer.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::rc::Rc;
+    /// use std::cell::RefCell;
+    /// use svgdom::{Document, ElementId, AttributeId, Mutation};
+    ///
+    /// let mut doc = Document::from_str("<svg/>").unwrap();
+    ///
+    /// let log = Rc::new(RefCell::new(Vec::new()));
+    /// let log2 = log.clone();
+    /// doc.add_observer(move |m: &Mutation| log2.borrow_mut().push(m.clone()));
+    ///
+    /// let mut svg = doc.svg_element().unwrap();
+    /// svg.set_attribute((AttributeId::Width, 10.0));
+    /// let rect = doc.create_element(ElementId::Rect);
+    /// svg.append(&rect);
+    ///
+    /// assert_eq!(log.borrow().len(), 2);
+    /// ```
+    pub fn add_observer<F>(&mut self, f: F) -> ObserverId
+        where F: FnMut(&Mutation) + 'static
+    {
+        let mut root = self.root.0.borrow_mut();
+        if root.observers.is_none() {
+            root.observers = Some(Rc::new(RefCell::new(Observers::new())));
+        }
+
+        let id = root.observers.as_ref().unwrap().borrow_mut().add(Box::new(f));
+        id
+    }
+
+    /// Unregisters a document observer.
+    ///
+    /// Returns `false` if an observer with such ID was not found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from an observer.
+    pub fn remove_observer(&mut self, id: ObserverId) -> bool {
+        let mut root = self.root.0.borrow_mut();
+
+        let (removed, is_empty) = match root.observers {
+            Some(ref observers) => {
+                let mut observers = observers.borrow_mut();
+                (observers.remove(id), observers.is_empty())
+            }
+            None => return false,
+        };
+
+        if is_empty {
+            root.observers = None;
+        }
+
+        removed
+    }
+
     /// Returns an iterator over descendants.
     pub fn descendants(&self) -> Descendants {
         self.root.descendants()
@@ -213,10 +639,38 @@ impl Document {
             attributes: Attributes::new(),
             linked_nodes: Vec::new(),
             text: text,
+            user_data: UserData::new(),
+            observers: None,
         })))
     }
 }
 
+/// Returns a list of nodes referenced by the node attributes.
+fn links_list(node: &Node) -> Vec<Node> {
+    let mut list = Vec::new();
+    for attr in node.attributes().iter() {
+        match attr.value {
+              AttributeValue::Link(ref link)
+            | AttributeValue::FuncLink(ref link) => list.push(link.clone()),
+            _ => {}
+        }
+    }
+
+    list
+}
+
+fn gen_unique_id(ids: &HashSet<String>, id: &str) -> String {
+    let mut n = 1;
+    loop {
+        let new_id = format!("{}_{}", id, n);
+        if !ids.contains(&new_id) {
+            return new_id;
+        }
+
+        n += 1;
+    }
+}
+
 impl WriteBuffer for Document {
     fn write_buf_opt(&self, opt: &WriteOptions, buf: &mut Vec<u8>) {
         writer::write_dom(self, opt, buf);
@@ -10,20 +10,20 @@ use {
 /// This trait contains methods that check element's type according to the
 /// [SVG spec](https://www.w3.org/TR/SVG/intro.html#Definitions).
 ///
-/// Note that all methods works with `Node` type and will return `false`
-/// if node's type is not equal to `NodeType::Element`.
+/// It's implemented for `ElementId` and `Node`. Note that methods of the `Node`
+/// will return `false` if node's type is not equal to `NodeType::Element`.
 ///
 /// # Panics
 ///
-/// All methods panics if the node is currently mutability borrowed.
+/// All `Node` methods panics if the node is currently mutability borrowed.
 pub trait ElementType {
     /// Returns true if the current node is referenced.
     ///
     /// Referenced elements are elements that do not render by itself,
     /// rather defines rendering properties for other.
     ///
-    /// List: `altGlyphDef`, `clipPath`, `cursor`, `filter`, `linearGradient`, `marker`,
-    /// `mask`, `pattern`, `radialGradient` and `symbol`.
+    /// List: `altGlyphDef`, `clipPath`, `cursor`, `filter`, `hatch`, `linearGradient`, `marker`,
+    /// `mask`, `meshgradient`, `pattern`, `radialGradient`, `solidcolor` and `symbol`.
     ///
     /// Details: https://www.w3.org/TR/SVG/struct.html#Head
     ///
@@ -78,7 +78,7 @@ pub trait ElementType {
 
     /// Returns true if the current node is a graphic element.
     ///
-    /// List: `circle`, `ellipse`, `image`, `line`, `path`, `polygon`, `polyline`, `rect`,
+    /// List: `circle`, `ellipse`, `image`, `line`, `mesh`, `path`, `polygon`, `polyline`, `rect`,
     /// `text` and `use`.
     ///
     /// Details: https://www.w3.org/TR/SVG/intro.html#TermGraphicsElement
@@ -86,36 +86,35 @@ pub trait ElementType {
 
     /// Returns true if the current node is a gradient element.
     ///
-    /// List: `linearGradient`, `radialGradient`.
+    /// List: `linearGradient`, `meshgradient`, `radialGradient`.
     fn is_gradient(&self) -> bool;
 }
 
 macro_rules! is_func {
     ($name:ident, $($pattern:tt)+) => (
         fn $name(&self) -> bool {
-            if let Some(id) = self.tag_id() {
-                match id {
-                    $($pattern)+ => true,
-                    _ => false
-                }
-            } else {
-                false
+            match *self {
+                $($pattern)+ => true,
+                _ => false
             }
         }
     )
 }
 
-impl ElementType for Node {
+impl ElementType for ElementId {
     is_func!(is_referenced,
           ElementId::AltGlyphDef
         | ElementId::ClipPath
         | ElementId::Cursor
         | ElementId::Filter
+        | ElementId::Hatch
         | ElementId::LinearGradient
         | ElementId::Marker
         | ElementId::Mask
+        | ElementId::MeshGradient
         | ElementId::Pattern
         | ElementId::RadialGradient
+        | ElementId::SolidColor
         | ElementId::Symbol);
 
     is_func!(is_basic_shape,
@@ -166,6 +165,7 @@ impl ElementType for Node {
         | ElementId::Ellipse
         | ElementId::Image
         | ElementId::Line
+        | ElementId::Mesh
         | ElementId::Path
         | ElementId::Polygon
         | ElementId::Polyline
@@ -175,5 +175,28 @@ impl ElementType for Node {
 
     is_func!(is_gradient,
           ElementId::LinearGradient
+        | ElementId::MeshGradient
         | ElementId::RadialGradient);
 }
+
+macro_rules! is_node_func {
+    ($name:ident) => (
+        fn $name(&self) -> bool {
+            match self.tag_id() {
+                Some(id) => id.$name(),
+                None => false,
+            }
+        }
+    )
+}
+
+impl ElementType for Node {
+    is_node_func!(is_referenced);
+    is_node_func!(is_basic_shape);
+    is_node_func!(is_shape);
+    is_node_func!(is_container);
+    is_node_func!(is_text_content);
+    is_node_func!(is_text_content_child);
+    is_node_func!(is_graphic);
+    is_node_func!(is_gradient);
+}
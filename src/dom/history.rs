@@ -0,0 +1,277 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+
+use {
+    Document,
+    Mutation,
+    Node,
+    ObserverId,
+};
+
+struct State {
+    /// Mutations of the current transaction.
+    log: Vec<Mutation>,
+    /// Log positions at which the nested transactions were started.
+    marks: Vec<usize>,
+    /// Mutations should not be recorded. Used during the rollback.
+    ignore: bool,
+    undo: Vec<Vec<Mutation>>,
+    redo: Vec<Vec<Mutation>>,
+}
+
+/// A transactional undo/redo log of the `Document`.
+///
+/// All modifications made between [`begin`] and [`commit`] are recorded as a single transaction,
+/// which can be reverted by [`undo`] and reapplied by [`redo`].
+/// Transactions can be nested. Only the outermost transaction is recorded.
+///
+/// Only modifications reported to the document observers are recorded.
+/// See [`Document::add_observer`] for details.
+///
+/// Modifications made outside of a transaction invalidate the log,
+/// so undo and redo stacks will be cleared.
+///
+/// The log is detached from the document when dropped.
+///
+/// # Examples
+/// ```
+/// use svgdom::{Document, ElementId, History};
+///
+/// let mut doc = Document::from_str("<svg/>").unwrap();
+/// let mut history = History::new(&mut doc);
+///
+/// history.begin();
+/// let rect = doc.create_element(ElementId::Rect);
+/// doc.svg_element().unwrap().append(&rect);
+/// history.commit();
+///
+/// assert_eq!(doc.to_string(), "<svg>\n    <rect/>\n</svg>\n");
+///
+/// history.undo();
+/// assert_eq!(doc.to_string(), "<svg/>\n");
+///
+/// history.redo();
+/// assert_eq!(doc.to_string(), "<svg>\n    <rect/>\n</svg>\n");
+/// ```
+///
+/// [`begin`]: #method.begin
+/// [`commit`]: #method.commit
+/// [`undo`]: #method.undo
+/// [`redo`]: #method.redo
+/// [`Document::add_observer`]: struct.Document.html#method.add_observer
+pub struct History {
+    doc: Document,
+    observer: ObserverId,
+    state: Rc<RefCell<State>>,
+}
+
+impl History {
+    /// Constructs a new `History` and attaches it to the `Document`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from a document observer.
+    pub fn new(doc: &mut Document) -> History {
+        let state = Rc::new(RefCell::new(State {
+            log: Vec::new(),
+            marks: Vec::new(),
+            ignore: false,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }));
+
+        let state2 = state.clone();
+        let observer = doc.add_observer(move |m: &Mutation| {
+            let mut s = state2.borrow_mut();
+            if s.ignore {
+                return;
+            }
+
+            if s.marks.is_empty() {
+                s.undo.clear();
+                s.redo.clear();
+            } else {
+                s.log.push(m.clone());
+            }
+        });
+
+        History {
+            doc: Document { root: doc.root() },
+            observer: observer,
+            state: state,
+        }
+    }
+
+    /// Starts a new transaction.
+    ///
+    /// If a transaction is already started, then a nested one will be created.
+    pub fn begin(&mut self) {
+        let mut s = self.state.borrow_mut();
+        let len = s.log.len();
+        s.marks.push(len);
+    }
+
+    /// Finishes the current transaction.
+    ///
+    /// When the outermost transaction is finished, all its modifications are pushed
+    /// to the undo stack as a single step and the redo stack is cleared.
+    /// Transactions without modifications are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no active transaction.
+    pub fn commit(&mut self) {
+        let mut s = self.state.borrow_mut();
+        s.marks.pop().expect("no active transaction");
+
+        if s.marks.is_empty() && !s.log.is_empty() {
+            let log = mem::replace(&mut s.log, Vec::new());
+            s.undo.push(log);
+            s.redo.clear();
+        }
+    }
+
+    /// Reverts all modifications of the current transaction and finishes it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is no active transaction.
+    /// - Panics if any of the modified nodes are currently borrowed.
+    pub fn rollback(&mut self) {
+        let log = {
+            let mut s = self.state.borrow_mut();
+            let mark = s.marks.pop().expect("no active transaction");
+            s.ignore = true;
+            s.log.split_off(mark)
+        };
+
+        revert(log);
+
+        self.state.borrow_mut().ignore = false;
+    }
+
+    /// Reverts the last transaction.
+    ///
+    /// Returns `false` if the undo stack is empty.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is an active transaction.
+    /// - Panics if any of the modified nodes are currently borrowed.
+    pub fn undo(&mut self) -> bool {
+        assert!(!self.is_in_transaction(), "undo during a transaction");
+
+        let log = self.state.borrow_mut().undo.pop();
+        match log {
+            Some(log) => {
+                let log = self.replay(log);
+                self.state.borrow_mut().redo.push(log);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reapplies the last reverted transaction.
+    ///
+    /// Returns `false` if the redo stack is empty.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is an active transaction.
+    /// - Panics if any of the modified nodes are currently borrowed.
+    pub fn redo(&mut self) -> bool {
+        assert!(!self.is_in_transaction(), "redo during a transaction");
+
+        let log = self.state.borrow_mut().redo.pop();
+        match log {
+            Some(log) => {
+                let log = self.replay(log);
+                self.state.borrow_mut().undo.push(log);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if there is an active transaction.
+    pub fn is_in_transaction(&self) -> bool {
+        !self.state.borrow().marks.is_empty()
+    }
+
+    /// Returns `true` if the undo stack is not empty.
+    pub fn can_undo(&self) -> bool {
+        !self.state.borrow().undo.is_empty()
+    }
+
+    /// Returns `true` if the redo stack is not empty.
+    pub fn can_redo(&self) -> bool {
+        !self.state.borrow().redo.is_empty()
+    }
+
+    /// Clears undo and redo stacks.
+    pub fn clear(&mut self) {
+        let mut s = self.state.borrow_mut();
+        s.undo.clear();
+        s.redo.clear();
+    }
+
+    /// Reverts the `log` and returns the modifications made during the reverting.
+    ///
+    /// Reverting them will reapply the `log`.
+    fn replay(&mut self, log: Vec<Mutation>) -> Vec<Mutation> {
+        self.state.borrow_mut().marks.push(0);
+        revert(log);
+
+        let mut s = self.state.borrow_mut();
+        s.marks.pop();
+        mem::replace(&mut s.log, Vec::new())
+    }
+}
+
+impl Drop for History {
+    fn drop(&mut self) {
+        self.doc.remove_observer(self.observer);
+    }
+}
+
+/// Reverts mutations in reverse order.
+fn revert(log: Vec<Mutation>) {
+    for m in log.into_iter().rev() {
+        match m {
+            Mutation::Insert { mut node, old_parent, old_next_sibling } => {
+                match old_parent {
+                    Some(parent) => restore_position(node, parent, old_next_sibling),
+                    None => node.detach(),
+                }
+            }
+            Mutation::Detach { node, old_parent, old_next_sibling } => {
+                restore_position(node, old_parent, old_next_sibling);
+            }
+            Mutation::Attribute { mut node, name, old_attribute } => {
+                match old_attribute {
+                    Some(attr) => node.set_attribute(attr),
+                    None => node.remove_attribute(name.into_ref()),
+                }
+            }
+            Mutation::Text { mut node, old_value } => {
+                node.set_text(&old_value);
+            }
+            Mutation::Id { mut node, old_value } => {
+                node.set_id(old_value);
+            }
+        }
+    }
+}
+
+fn restore_position(node: Node, mut parent: Node, next_sibling: Option<Node>) {
+    match next_sibling {
+        Some(mut next) => next.insert_before(&node),
+        None => parent.append(&node),
+    }
+}
@@ -0,0 +1,529 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+use std::mem;
+
+use {
+    Attribute,
+    AttributeId,
+    AttributeName,
+    AttributeValue,
+    Change,
+    Document,
+    Node,
+    NodeType,
+};
+use super::diff::{
+    self,
+    Matching,
+};
+
+/// A conflict detected by a three-way merge.
+///
+/// The version from `ours` is always kept in the merged document.
+///
+/// See [`Document::merge`] for details.
+///
+/// [`Document::merge`]: struct.Document.html#method.merge
+#[derive(Clone,Debug)]
+pub enum Conflict {
+    /// Both sides changed the same attribute to different values.
+    Attribute {
+        /// A node from the `base` document.
+        base: Node,
+        /// A node from the `ours` document.
+        ours: Node,
+        /// A node from the `theirs` document.
+        theirs: Node,
+        /// An attribute name.
+        name: AttributeName,
+    },
+    /// Both sides changed the same element ID to different values.
+    Id {
+        /// A node from the `base` document.
+        base: Node,
+        /// A node from the `ours` document.
+        ours: Node,
+        /// A node from the `theirs` document.
+        theirs: Node,
+    },
+    /// Both sides changed the same text data to different values.
+    Text {
+        /// A node from the `base` document.
+        base: Node,
+        /// A node from the `ours` document.
+        ours: Node,
+        /// A node from the `theirs` document.
+        theirs: Node,
+    },
+    /// Both sides moved the same node to different positions.
+    Move {
+        /// A node from the `base` document.
+        base: Node,
+        /// A node from the `ours` document.
+        ours: Node,
+        /// A node from the `theirs` document.
+        theirs: Node,
+    },
+    /// One side removed a node that was modified or referenced by the other side.
+    ///
+    /// `ours` is `None` if the node was removed by `ours`.
+    /// `theirs` is `None` if the node was removed by `theirs`.
+    Remove {
+        /// A node from the `base` document.
+        base: Node,
+        /// A node from the `ours` document.
+        ours: Option<Node>,
+        /// A node from the `theirs` document.
+        theirs: Option<Node>,
+    },
+    /// A node inserted by `theirs` has an ID that is already used by the merged document.
+    Insert {
+        /// A node from the `theirs` document.
+        theirs: Node,
+    },
+    /// A link attribute set by `theirs` references an element that doesn't exist
+    /// in the merged document.
+    Link {
+        /// A node from the `theirs` document.
+        theirs: Node,
+        /// An attribute name.
+        name: AttributeId,
+    },
+}
+
+/// A link that should be set after all nodes are copied.
+struct PendingLink {
+    node: Node,
+    attribute: Attribute,
+    /// A referenced node from the `ours` or `theirs` document.
+    target: Node,
+    from_theirs: Option<Node>,
+}
+
+struct Merger<'a> {
+    ours_m: &'a Matching,
+    theirs_m: &'a Matching,
+    doc: Document,
+    /// Maps nodes from the `ours` and `theirs` documents to the merged document.
+    to_result: HashMap<Node, Node>,
+    links: Vec<PendingLink>,
+    conflicts: Vec<Conflict>,
+    /// Nodes from the `base` document, which were removed by `ours`
+    /// and already reported as conflicts.
+    reported: HashSet<Node>,
+    /// IDs used by the merged document.
+    ids: HashSet<String>,
+}
+
+pub fn merge(base: &Document, ours: &Document, theirs: &Document) -> (Document, Vec<Conflict>) {
+    let ours_m = diff::match_nodes(base, ours);
+    let theirs_m = diff::match_nodes(base, theirs);
+
+    let mut merger = Merger {
+        ours_m: &ours_m,
+        theirs_m: &theirs_m,
+        doc: Document::new(),
+        to_result: HashMap::new(),
+        links: Vec::new(),
+        conflicts: Vec::new(),
+        reported: HashSet::new(),
+        ids: HashSet::new(),
+    };
+
+    // The merged document is a copy of 'ours' with changes from 'theirs' applied.
+    let mut root = merger.doc.root();
+    merger.to_result.insert(ours.root(), root.clone());
+    for child in ours.children() {
+        let node = merger.copy_subtree(&child, false);
+        root.append(&node);
+    }
+
+    merger.ids = merger.doc.descendants()
+        .filter(|n| n.has_id())
+        .map(|n| n.id().clone())
+        .collect();
+
+    // Base nodes touched by 'ours'.
+    let mut touched = HashSet::new();
+    for change in diff::diff_matched(base, ours, &ours_m) {
+        match change {
+              Change::Attribute { old, .. }
+            | Change::Id { old, .. }
+            | Change::Text { old, .. } => {
+                touched.insert(old);
+            }
+            Change::Moved { old, new } => {
+                touched.insert(old);
+                if let Some(parent) = new.parent().and_then(|p| ours_m.new_to_old.get(&p).cloned()) {
+                    touched.insert(parent);
+                }
+            }
+            Change::Removed(node) => {
+                touched.insert(node);
+            }
+            Change::Inserted(node) => {
+                if let Some(parent) = node.parent().and_then(|p| ours_m.new_to_old.get(&p).cloned()) {
+                    touched.insert(parent);
+                }
+            }
+        }
+    }
+
+    let mut removed = Vec::new();
+    let mut inserted = HashSet::new();
+    let mut moved = HashMap::new();
+    let mut modified = Vec::new();
+    for change in diff::diff_matched(base, theirs, &theirs_m) {
+        match change {
+            Change::Removed(node) => removed.push(node),
+            Change::Inserted(node) => { inserted.insert(node); }
+            Change::Moved { old, new } => { moved.insert(new, old); }
+            change => modified.push(change),
+        }
+    }
+
+    // Structure changes are applied in the 'theirs' document order,
+    // so the previous siblings are always in place.
+    for node in theirs.descendants() {
+        if inserted.contains(&node) {
+            merger.insert(&node);
+        } else if let Some(base_node) = moved.get(&node) {
+            merger.move_node(base_node, &node, &touched);
+        }
+    }
+
+    for change in modified {
+        merger.modify(change);
+    }
+
+    merger.resolve_links();
+
+    for base_node in removed {
+        merger.remove(&base_node, &touched);
+    }
+
+    (merger.doc, merger.conflicts)
+}
+
+impl<'a> Merger<'a> {
+    /// Returns a node from the merged document that corresponds to the `theirs` node.
+    fn theirs_to_result(&self, node: &Node) -> Option<Node> {
+        if let Some(n) = self.to_result.get(node) {
+            return Some(n.clone());
+        }
+
+        match self.theirs_m.new_to_old.get(node) {
+            Some(base) => self.base_to_result(base),
+            None => None,
+        }
+    }
+
+    /// Returns a node from the merged document that corresponds to the `base` node.
+    fn base_to_result(&self, node: &Node) -> Option<Node> {
+        match self.ours_m.old_to_new.get(node) {
+            Some(ours) => self.to_result.get(ours).cloned(),
+            None => None,
+        }
+    }
+
+    /// Copies a node with all its children into the merged document.
+    fn copy_subtree(&mut self, node: &Node, is_theirs: bool) -> Node {
+        let mut copy = match node.node_type() {
+            NodeType::Element => {
+                let mut elem = self.doc.create_element(node.tag_name().unwrap().into_ref());
+                elem.set_id(node.id().clone());
+                elem
+            }
+            node_type => self.doc.create_node(node_type, &node.text()),
+        };
+
+        for attr in node.attributes().iter() {
+            self.set_attribute(&mut copy, attr, node, is_theirs);
+        }
+
+        self.to_result.insert(node.clone(), copy.clone());
+
+        for child in node.children() {
+            let child_copy = self.copy_subtree(&child, is_theirs);
+            copy.append(&child_copy);
+        }
+
+        copy
+    }
+
+    fn set_attribute(&mut self, node: &mut Node, attr: &Attribute, source: &Node, is_theirs: bool) {
+        match attr.value {
+              AttributeValue::Link(ref target)
+            | AttributeValue::FuncLink(ref target) => {
+                self.links.push(PendingLink {
+                    node: node.clone(),
+                    attribute: attr.clone(),
+                    target: target.clone(),
+                    from_theirs: if is_theirs { Some(source.clone()) } else { None },
+                });
+            }
+            _ => node.set_attribute(attr.clone()),
+        }
+    }
+
+    /// Returns a parent and a previous sibling in the merged document
+    /// that correspond to the position of the `theirs` node.
+    fn position(&self, node: &Node) -> Option<(Node, Option<Node>)> {
+        let parent = match node.parent().and_then(|p| self.theirs_to_result(&p)) {
+            Some(p) => p,
+            None => return None,
+        };
+
+        let mut prev = node.previous_sibling();
+        while let Some(p) = prev {
+            if let Some(r) = self.theirs_to_result(&p) {
+                if r.parent().as_ref() == Some(&parent) {
+                    return Some((parent, Some(r)));
+                }
+            }
+
+            prev = p.previous_sibling();
+        }
+
+        Some((parent, None))
+    }
+
+    fn place(&mut self, node: &Node, parent: Node, prev: Option<Node>) {
+        match prev {
+            Some(mut prev) => prev.insert_after(node),
+            None => parent.clone().prepend(node),
+        }
+    }
+
+    fn insert(&mut self, node: &Node) {
+        let (parent, prev) = match self.position(node) {
+            Some(v) => v,
+            None => {
+                self.report_removed_parent(node);
+                return;
+            }
+        };
+
+        if node.descendants().any(|n| self.ids.contains(&*n.id())) {
+            self.conflicts.push(Conflict::Insert { theirs: node.clone() });
+            return;
+        }
+
+        for n in node.descendants().filter(|n| n.has_id()) {
+            self.ids.insert(n.id().clone());
+        }
+
+        let copy = self.copy_subtree(node, true);
+        self.place(&copy, parent, prev);
+    }
+
+    fn move_node(&mut self, base: &Node, node: &Node, touched: &HashSet<Node>) {
+        let result_node = match self.base_to_result(base) {
+            Some(n) => n,
+            None => {
+                self.report_removed(base, node);
+                return;
+            }
+        };
+
+        let ours = self.ours_m.old_to_new[base].clone();
+        let ours_moved = touched.contains(base) && !is_same_position(base, &ours, self.ours_m);
+
+        let (parent, prev) = match self.position(node) {
+            Some(v) => v,
+            None => {
+                self.report_removed_parent(node);
+                return;
+            }
+        };
+
+        // Both sides moved the node to the same place.
+        if result_node.parent().as_ref() == Some(&parent) && result_node.previous_sibling() == prev {
+            return;
+        }
+
+        // The node can't be moved into itself.
+        let is_loop = parent.parents_with_self().any(|p| p == result_node);
+
+        if ours_moved || is_loop {
+            self.conflicts.push(Conflict::Move {
+                base: base.clone(),
+                ours: ours,
+                theirs: node.clone(),
+            });
+            return;
+        }
+
+        self.place(&result_node, parent, prev);
+    }
+
+    fn modify(&mut self, change: Change) {
+        let (base, theirs) = match change {
+              Change::Attribute { ref old, ref new, .. }
+            | Change::Id { ref old, ref new }
+            | Change::Text { ref old, ref new } => (old.clone(), new.clone()),
+            _ => unreachable!(),
+        };
+
+        let mut result_node = match self.base_to_result(&base) {
+            Some(n) => n,
+            None => {
+                self.report_removed(&base, &theirs);
+                return;
+            }
+        };
+
+        let ours = self.ours_m.old_to_new[&base].clone();
+
+        match change {
+            Change::Attribute { name, .. } => {
+                let base_attr = base.attributes().get(name.into_ref()).cloned();
+                let ours_attr = ours.attributes().get(name.into_ref()).cloned();
+                let theirs_attr = theirs.attributes().get(name.into_ref()).cloned();
+
+                if is_same_attribute(&ours_attr, &theirs_attr) {
+                    return;
+                }
+
+                if !is_same_attribute(&base_attr, &ours_attr) {
+                    self.conflicts.push(Conflict::Attribute {
+                        base: base,
+                        ours: ours,
+                        theirs: theirs,
+                        name: name,
+                    });
+                    return;
+                }
+
+                match theirs_attr {
+                    Some(attr) => self.set_attribute(&mut result_node, &attr, &theirs, true),
+                    None => result_node.remove_attribute(name.into_ref()),
+                }
+            }
+            Change::Id { .. } => {
+                if *ours.id() == *theirs.id() {
+                    return;
+                }
+
+                if *base.id() != *ours.id() {
+                    self.conflicts.push(Conflict::Id { base: base, ours: ours, theirs: theirs });
+                    return;
+                }
+
+                result_node.set_id(theirs.id().clone());
+            }
+            Change::Text { .. } => {
+                if *ours.text() == *theirs.text() {
+                    return;
+                }
+
+                if *base.text() != *ours.text() {
+                    self.conflicts.push(Conflict::Text { base: base, ours: ours, theirs: theirs });
+                    return;
+                }
+
+                result_node.set_text(&theirs.text());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn resolve_links(&mut self) {
+        for mut link in mem::replace(&mut self.links, Vec::new()) {
+            let target = match link.from_theirs {
+                Some(_) => self.theirs_to_result(&link.target),
+                None => self.to_result.get(&link.target).cloned(),
+            };
+
+            let aid = link.attribute.id().unwrap();
+            match target {
+                Some(target) => {
+                    let mut attr = link.attribute;
+                    attr.value = match attr.value {
+                        AttributeValue::Link(_) => AttributeValue::Link(target),
+                        _ => AttributeValue::FuncLink(target),
+                    };
+                    link.node.set_attribute(attr);
+                }
+                None => {
+                    if let Some(theirs) = link.from_theirs {
+                        self.conflicts.push(Conflict::Link { theirs: theirs, name: aid });
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, base: &Node, touched: &HashSet<Node>) {
+        let ours = match self.ours_m.old_to_new.get(base) {
+            Some(n) => n.clone(),
+            // Removed by both sides.
+            None => return,
+        };
+
+        let mut result_node = match self.to_result.get(&ours) {
+            Some(n) => n.clone(),
+            None => return,
+        };
+
+        let is_modified = base.descendants().any(|n| touched.contains(&n));
+
+        // Check that the node is not referenced by the remaining nodes.
+        let is_used = result_node.descendants().any(|n| {
+            n.linked_nodes().any(|l| !l.parents_with_self().any(|p| p == result_node))
+        });
+
+        if is_modified || is_used {
+            self.conflicts.push(Conflict::Remove {
+                base: base.clone(),
+                ours: Some(ours),
+                theirs: None,
+            });
+            return;
+        }
+
+        result_node.remove();
+    }
+
+    /// Reports a modification by `theirs` of a node removed by `ours`.
+    fn report_removed(&mut self, base: &Node, theirs: &Node) {
+        if self.reported.insert(base.clone()) {
+            self.conflicts.push(Conflict::Remove {
+                base: base.clone(),
+                ours: None,
+                theirs: Some(theirs.clone()),
+            });
+        }
+    }
+
+    /// Reports an insertion by `theirs` into a node removed by `ours`.
+    fn report_removed_parent(&mut self, node: &Node) {
+        let parent = node.parent().unwrap();
+        // A parent can be inserted by `theirs` and not copied. It's already reported.
+        let base = self.theirs_m.new_to_old.get(&parent).cloned();
+        if let Some(base) = base {
+            self.report_removed(&base, &parent);
+        }
+    }
+}
+
+fn is_same_attribute(a: &Option<Attribute>, b: &Option<Attribute>) -> bool {
+    match (a.as_ref(), b.as_ref()) {
+        (Some(a), Some(b)) => a.value.fuzzy_eq(&b.value),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Checks that the `new` node has the same parent and previous sibling as the `old` one.
+fn is_same_position(old: &Node, new: &Node, m: &Matching) -> bool {
+    let parent = old.parent().and_then(|p| m.old_to_new.get(&p).cloned());
+    let prev = old.previous_sibling().and_then(|p| m.old_to_new.get(&p).cloned());
+    parent == new.parent() && prev == new.previous_sibling()
+}
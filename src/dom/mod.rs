@@ -2,11 +2,26 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+pub use self::diff::Change;
 pub use self::document::Document;
 pub use self::element_type::ElementType;
+pub use self::history::History;
 pub use self::iterators::*;
+pub use self::merge::Conflict;
 pub use self::node::Node;
 pub use self::node_type::NodeType;
+pub use self::observer::{
+    Mutation,
+    ObserverId,
+};
+pub use self::references::{
+    Reference,
+    ReferenceGraph,
+};
+pub use self::validation::{
+    Violation,
+    ViolationKind,
+};
 
 use {Name, NameRef, ElementId};
 /// Type alias for `NameRef<ElementId>`.
@@ -14,9 +29,17 @@ pub type TagNameRef<'a> = NameRef<'a, ElementId>;
 /// Type alias for `Name<ElementId>`.
 pub type TagName = Name<ElementId>;
 
+mod diff;
 mod document;
 mod element_type;
+mod history;
 mod iterators;
+mod merge;
 mod node;
 mod node_data;
 mod node_type;
+mod observer;
+mod references;
+mod transforms;
+mod user_data;
+mod validation;
@@ -2,39 +2,61 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use std::any::Any;
 use std::cell::{
     Ref,
+    RefCell,
     RefMut
 };
 use std::rc::Rc;
 use std::fmt;
+use std::mem;
+use std::hash::{
+    Hash,
+    Hasher,
+};
 
 use error::Result;
 use {
+    AspectRatio,
     Attribute,
     AttributeId,
     AttributeNameRef,
     Attributes,
     AttributeValue,
     Children,
+    Color,
+    ConversionContext,
     Descendants,
     Document,
     ElementId,
     ErrorKind,
+    Length,
+    LengthDirection,
+    LengthUnit,
     LinkedNodes,
+    Mutation,
     Name,
     NameRef,
     NodeType,
     Parents,
+    Rect,
     SvgId,
     TagName,
     TagNameRef,
+    Transform,
     Traverse,
+    ValueId,
+    path,
 };
 use super::node_data::{
     Link,
     NodeData,
 };
+use super::observer::{
+    self,
+    ObserversLink,
+};
 
 macro_rules! try_opt {
     ($expr: expr) => {
@@ -60,7 +82,7 @@ impl<'a, N, V> From<(N, V)> for Attribute
 
 impl<'a> From<(AttributeId, Node)> for Attribute {
     fn from(v: (AttributeId, Node)) -> Self {
-        if v.0 == AttributeId::XlinkHref {
+        if v.0 == AttributeId::XlinkHref || v.0 == AttributeId::Href {
             Attribute::new(v.0, AttributeValue::Link(v.1))
         } else {
             Attribute::new(v.0, AttributeValue::FuncLink(v.1))
@@ -242,7 +264,21 @@ impl Node {
     ///
     /// Panics if the node or one of its adjoining nodes is currently borrowed.
     pub fn detach(&mut self) {
-        self.0.borrow_mut().detach();
+        match self.observers() {
+            Some(observers) => {
+                let (old_parent, old_next_sibling) = self.position();
+                self.0.borrow_mut().detach();
+
+                if let Some(old_parent) = old_parent {
+                    observer::notify(&observers, Mutation::Detach {
+                        node: self.clone(),
+                        old_parent: old_parent,
+                        old_next_sibling: old_next_sibling,
+                    });
+                }
+            }
+            None => self.0.borrow_mut().detach(),
+        }
     }
 
     /// Removes this node and all it children from the tree.
@@ -357,6 +393,7 @@ impl Node {
     /// Returns a copy of a current node without children.
     ///
     /// All attributes except `id` will be copied, because `id` must be unique.
+    /// User data is not copied.
     ///
     /// # Panics
     ///
@@ -381,6 +418,7 @@ impl Node {
     /// Returns a deep copy of a current node with all it's children.
     ///
     /// All attributes except `id` will be copied, because `id` must be unique.
+    /// User data is not copied.
     ///
     /// # Panics
     ///
@@ -408,6 +446,10 @@ impl Node {
     ///
     /// Panics if the node, the new child, or one of their adjoining nodes is currently borrowed.
     pub fn append(&mut self, new_child: &Node) {
+        self.insert_with(new_child, Node::append_impl);
+    }
+
+    fn append_impl(&mut self, new_child: &Node) {
         let mut this = self.0.borrow_mut();
         let mut last = None;
         let nc = new_child.clone();
@@ -441,6 +483,10 @@ impl Node {
     ///
     /// Panics if the node, the new child, or one of their adjoining nodes is currently borrowed.
     pub fn prepend(&mut self, new_child: &Node) {
+        self.insert_with(new_child, Node::prepend_impl);
+    }
+
+    fn prepend_impl(&mut self, new_child: &Node) {
         let mut this = self.0.borrow_mut();
         {
             let mut child = new_child.0.borrow_mut();
@@ -470,6 +516,10 @@ impl Node {
     ///
     /// Panics if the node, the new sibling, or one of their adjoining nodes is currently borrowed.
     pub fn insert_after(&mut self, new_sibling: &Node) {
+        self.insert_with(new_sibling, Node::insert_after_impl);
+    }
+
+    fn insert_after_impl(&mut self, new_sibling: &Node) {
         // TODO: add an example, since we need to detach 'new_sibling'
         //       before passing it to this method
         let mut this = self.0.borrow_mut();
@@ -506,6 +556,10 @@ impl Node {
     ///
     /// Panics if the node, the new sibling, or one of their adjoining nodes is currently borrowed.
     pub fn insert_before(&mut self, new_sibling: &Node) {
+        self.insert_with(new_sibling, Node::insert_before_impl);
+    }
+
+    fn insert_before_impl(&mut self, new_sibling: &Node) {
         let mut this = self.0.borrow_mut();
         let mut prev_opt = None;
         {
@@ -537,6 +591,50 @@ impl Node {
         }
     }
 
+    /// Detaches the `new_node` and inserts it using `f`.
+    ///
+    /// The `new_node` must be detached before `f` borrows the current node,
+    /// because they can be siblings.
+    fn insert_with<F>(&mut self, new_node: &Node, f: F)
+        where F: FnOnce(&mut Node, &Node)
+    {
+        match self.observers() {
+            Some(observers) => {
+                let (old_parent, old_next_sibling) = new_node.position();
+                new_node.0.borrow_mut().detach();
+                f(self, new_node);
+
+                observer::notify(&observers, Mutation::Insert {
+                    node: new_node.clone(),
+                    old_parent: old_parent,
+                    old_next_sibling: old_next_sibling,
+                });
+            }
+            None => {
+                new_node.0.borrow_mut().detach();
+                f(self, new_node);
+            }
+        }
+    }
+
+    /// Returns a parent and a next sibling of the node.
+    fn position(&self) -> (Option<Node>, Option<Node>) {
+        (self.parent(), self.next_sibling())
+    }
+
+    /// Returns observers of the document that owns this node.
+    fn observers(&self) -> Option<ObserversLink> {
+        let b = self.0.borrow();
+        match b.doc {
+            Some(ref doc) => {
+                let root = try_opt!(doc.upgrade());
+                let observers = root.borrow().observers.clone();
+                observers
+            }
+            None => b.observers.clone(),
+        }
+    }
+
     fn update_parent<F>(this: &RefMut<NodeData>, mut f: F)
         where F: FnMut(RefMut<NodeData>)
     {
@@ -587,8 +685,18 @@ impl Node {
     /// Panics if the node is currently mutably borrowed.
     pub fn set_text(&mut self, text: &str) {
         debug_assert_ne!(self.node_type(), NodeType::Element);
-        let mut b = self.0.borrow_mut();
-        b.text = text.to_owned();
+        let observers = self.observers();
+        let old_value = mem::replace(&mut self.0.borrow_mut().text, text.to_owned());
+        if old_value == text {
+            return;
+        }
+
+        if let Some(observers) = observers {
+            observer::notify(&observers, Mutation::Text {
+                node: self.clone(),
+                old_value: old_value,
+            });
+        }
     }
 
     /// Returns an ID of the element node.
@@ -619,7 +727,18 @@ impl Node {
     pub fn set_id<S: Into<String>>(&mut self, id: S) {
         // TODO: check that it's unique.
         debug_assert_eq!(self.node_type(), NodeType::Element);
-        self.0.borrow_mut().id = id.into();
+        let observers = self.observers();
+        let old_value = mem::replace(&mut self.0.borrow_mut().id, id.into());
+        if old_value == *self.0.borrow().id {
+            return;
+        }
+
+        if let Some(observers) = observers {
+            observer::notify(&observers, Mutation::Id {
+                node: self.clone(),
+                old_value: old_value,
+            });
+        }
     }
 
     /// Returns `true` if node has an `Element` type and an SVG tag name.
@@ -900,6 +1019,25 @@ impl Node {
     }
 
     fn set_attribute_checked_impl(&mut self, attr: Attribute) -> Result<()> {
+        match self.observers() {
+            Some(observers) => {
+                let name = attr.name.clone();
+                let old_attribute = self.attributes().get(name.into_ref()).cloned();
+                self.set_attribute_impl(attr)?;
+
+                observer::notify(&observers, Mutation::Attribute {
+                    node: self.clone(),
+                    name: name,
+                    old_attribute: old_attribute,
+                });
+
+                Ok(())
+            }
+            None => self.set_attribute_impl(attr),
+        }
+    }
+
+    fn set_attribute_impl(&mut self, attr: Attribute) -> Result<()> {
         // TODO: to error in _checked mode
         debug_assert_eq!(self.node_type(), NodeType::Element);
 
@@ -924,7 +1062,7 @@ impl Node {
         debug_assert!(!attr.is_link() && !attr.is_func_link());
 
         // we must remove existing attribute to prevent dangling links
-        self.remove_attribute(attr.name.into_ref());
+        self.remove_attribute_impl(attr.name.into_ref());
 
         let mut attrs = self.attributes_mut();
         attrs.insert(attr);
@@ -946,10 +1084,10 @@ impl Node {
         }
 
         // we must remove existing attribute to prevent dangling links
-        self.remove_attribute(id);
+        self.remove_attribute_impl(id);
 
         {
-            let a = if id == AttributeId::XlinkHref {
+            let a = if id == AttributeId::XlinkHref || id == AttributeId::Href {
                 Attribute::new(id, AttributeValue::Link(node.clone()))
             } else {
                 Attribute::new(id, AttributeValue::FuncLink(node.clone()))
@@ -1001,6 +1139,26 @@ impl Node {
     /// Panics if the node is currently borrowed.
     pub fn remove_attribute<'a, N>(&mut self, name: N)
         where AttributeNameRef<'a>: From<N>, N: Copy
+    {
+        match self.observers() {
+            Some(observers) => {
+                let old_attribute = self.attributes().get(name).cloned();
+                self.remove_attribute_impl(name);
+
+                if let Some(old_attribute) = old_attribute {
+                    observer::notify(&observers, Mutation::Attribute {
+                        node: self.clone(),
+                        name: old_attribute.name.clone(),
+                        old_attribute: Some(old_attribute),
+                    });
+                }
+            }
+            None => self.remove_attribute_impl(name),
+        }
+    }
+
+    fn remove_attribute_impl<'a, N>(&mut self, name: N)
+        where AttributeNameRef<'a>: From<N>, N: Copy
     {
         if !self.has_attribute(name) {
             return;
@@ -1073,6 +1231,416 @@ impl Node {
         let self_borrow = self.0.borrow();
         self_borrow.linked_nodes.len()
     }
+
+    /// Returns a transform from the `viewBox` coordinates to the viewport coordinates.
+    ///
+    /// Works only with `svg`, `symbol`, `marker` and `pattern` elements.
+    /// The viewport size is taken from the `width` and `height` attributes,
+    /// or from `markerWidth` and `markerHeight` for `marker`.
+    /// The viewport position, aka `x` and `y` attributes, is not applied.
+    ///
+    /// Returns `None` if the element doesn't have a `viewBox` attribute,
+    /// the `viewBox` has a zero size or the viewport size is not set in user units.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is currently mutably borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, Transform};
+    ///
+    /// let doc = Document::from_str(
+    ///     "<svg width='200' height='100' viewBox='0 0 20 20'/>").unwrap();
+    /// let svg = doc.svg_element().unwrap();
+    /// assert_eq!(svg.view_box_transform(), Some(Transform::new(5.0, 0.0, 0.0, 5.0, 50.0, 0.0)));
+    /// ```
+    pub fn view_box_transform(&self) -> Option<Transform> {
+        let (w_id, h_id) = match self.tag_id() {
+            Some(ElementId::Svg) | Some(ElementId::Symbol) | Some(ElementId::Pattern) => {
+                (AttributeId::Width, AttributeId::Height)
+            }
+            Some(ElementId::Marker) => (AttributeId::MarkerWidth, AttributeId::MarkerHeight),
+            _ => return None,
+        };
+
+        let attrs = self.attributes();
+
+        let view_box = match attrs.get_value(AttributeId::ViewBox) {
+            Some(&AttributeValue::ViewBox(r)) => r,
+            _ => return None,
+        };
+
+        let (width, height) = match (viewport_size(&attrs, w_id), viewport_size(&attrs, h_id)) {
+            (Some(w), Some(h)) => (w, h),
+            _ => return None,
+        };
+
+        aspect_ratio(&attrs).view_box_transform(view_box, width, height)
+    }
+
+    /// Returns a transform from the image coordinates to the `image` element viewport.
+    ///
+    /// Image size is unknown to the DOM, so it should be set via `width` and `height`.
+    /// It acts as a `viewBox` of `0 0 width height`.
+    ///
+    /// The viewport is taken from the `x`, `y`, `width` and `height` attributes.
+    ///
+    /// Returns `None` if the node is not an `image` element,
+    /// the image size is zero or the viewport is not set in user units.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is currently mutably borrowed.
+    pub fn image_transform(&self, width: f64, height: f64) -> Option<Transform> {
+        if !self.is_tag_name(ElementId::Image) {
+            return None;
+        }
+
+        let attrs = self.attributes();
+
+        let x = match attrs.get_value(AttributeId::X) {
+            Some(_) => try_opt!(viewport_size(&attrs, AttributeId::X)),
+            None => 0.0,
+        };
+
+        let y = match attrs.get_value(AttributeId::Y) {
+            Some(_) => try_opt!(viewport_size(&attrs, AttributeId::Y)),
+            None => 0.0,
+        };
+
+        let vw = try_opt!(viewport_size(&attrs, AttributeId::Width));
+        let vh = try_opt!(viewport_size(&attrs, AttributeId::Height));
+
+        let view_box = Rect::new(0.0, 0.0, width, height);
+        let mut ts = Transform::new(1.0, 0.0, 0.0, 1.0, x, y);
+        ts.append(&try_opt!(aspect_ratio(&attrs).view_box_transform(view_box, vw, vh)));
+        Some(ts)
+    }
+
+    /// Resolves the `currentColor` value for this node.
+    ///
+    /// Returns the value of the `color` attribute of the current node
+    /// or of the nearest ancestor that has one.
+    /// ICC colors are resolved into their sRGB fallback.
+    ///
+    /// Returns `None` if no `color` attribute was found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node or any of its ancestors is currently mutably borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, ElementId, Color};
+    ///
+    /// let doc = Document::from_str(
+    ///     "<svg color='red'><g><rect fill='currentColor'/></g></svg>").unwrap();
+    /// let rect = doc.descendants().find(|n| n.is_tag_name(ElementId::Rect)).unwrap();
+    /// assert_eq!(rect.current_color(), Some(Color::new(255, 0, 0)));
+    /// ```
+    pub fn current_color(&self) -> Option<Color> {
+        for node in self.parents_with_self() {
+            match node.attributes().get_value(AttributeId::Color) {
+                Some(&AttributeValue::Color(c)) => return Some(c),
+                Some(&AttributeValue::IccColor(ref c)) => return Some(c.color),
+                // 'inherit' and 'currentColor' are resolved by the parent.
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    /// Builds a context for converting the specified attribute length into user units.
+    ///
+    /// The viewport size is taken from the nearest `svg` ancestor's `viewBox`,
+    /// or from its `width` and `height` when there is no `viewBox`.
+    /// If the viewport size is unknown, like for the root `svg` element,
+    /// 100x100 is used.
+    ///
+    /// The font size is resolved from the `font-size` attributes of the current node
+    /// and its ancestors. For the `font-size` attribute itself, only ancestors are used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node or any of its ancestors is currently mutably borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, ElementId, AttributeId, AttributeValue};
+    ///
+    /// let doc = Document::from_str(
+    ///     "<svg viewBox='0 0 200 100'><rect width='50%' height='2em'/></svg>").unwrap();
+    /// let rect = doc.descendants().find(|n| n.is_tag_name(ElementId::Rect)).unwrap();
+    ///
+    /// let ctx = rect.conversion_context(AttributeId::Width);
+    /// let width = match rect.attributes().get_value(AttributeId::Width) {
+    ///     Some(&AttributeValue::Length(l)) => l.to_user_units(&ctx),
+    ///     _ => unreachable!(),
+    /// };
+    /// assert_eq!(width, 100.0);
+    /// ```
+    pub fn conversion_context(&self, id: AttributeId) -> ConversionContext {
+        let (viewport_width, viewport_height) = match self.parents().find(|n| {
+            n.is_tag_name(ElementId::Svg)
+        }) {
+            Some(svg) => svg_viewport(&svg),
+            None => {
+                let ctx = ConversionContext::default();
+                (ctx.viewport_width, ctx.viewport_height)
+            }
+        };
+
+        let font_size = if id == AttributeId::FontSize {
+            match self.parent() {
+                Some(parent) => resolve_font_size(&parent),
+                None => DEFAULT_FONT_SIZE,
+            }
+        } else {
+            resolve_font_size(self)
+        };
+
+        ConversionContext {
+            viewport_width: viewport_width,
+            viewport_height: viewport_height,
+            direction: length_direction(id),
+            font_size: font_size,
+            ..ConversionContext::default()
+        }
+    }
+
+    /// Converts a shape element into a path.
+    ///
+    /// Supports basic shapes: `rect` (including rounded one), `circle`, `ellipse`, `line`,
+    /// `polyline` and `polygon`. For a `path` element, a copy of the `d` attribute is returned.
+    ///
+    /// Lengths are converted into user units via [`conversion_context`].
+    ///
+    /// Returns `None` for other elements and for shapes that should not be rendered,
+    /// like a `rect` with zero width or a `polyline` with less than two points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node or any of its ancestors is currently mutably borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, ElementId};
+    ///
+    /// let doc = Document::from_str("<svg><rect x='10' width='20' height='30'/></svg>").unwrap();
+    /// let rect = doc.descendants().find(|n| n.is_tag_name(ElementId::Rect)).unwrap();
+    /// assert_eq!(rect.shape_to_path().unwrap().to_string(), "M 10 0 H 30 V 30 H 10 Z");
+    /// ```
+    ///
+    /// [`conversion_context`]: #method.conversion_context
+    pub fn shape_to_path(&self) -> Option<path::Path> {
+        shape_to_path(self)
+    }
+
+    /// Converts a basic shape element into a `path` element in place.
+    ///
+    /// The tag name is changed to `path`, shape geometry attributes are replaced
+    /// by the `d` attribute and all other attributes, like presentation attributes,
+    /// `id` and `transform`, are preserved. Since the node itself is not replaced,
+    /// all links to it remain valid.
+    ///
+    /// Returns `false` and leaves the node unchanged if it's not a basic shape
+    /// or if [`shape_to_path`] returns `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node or any of its ancestors is currently borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, ElementId};
+    ///
+    /// let doc = Document::from_str(
+    ///     "<svg><line id='line1' x2='10' y2='20' stroke='red'/></svg>").unwrap();
+    /// let mut line = doc.descendants().find(|n| n.is_tag_name(ElementId::Line)).unwrap();
+    /// assert!(line.convert_shape_to_path());
+    /// assert_eq!(doc.to_string(),
+    ///     "<svg>\n    <path id=\"line1\" d=\"M 0 0 L 10 20\" stroke=\"#ff0000\"/>\n</svg>\n");
+    /// ```
+    ///
+    /// [`shape_to_path`]: #method.shape_to_path
+    pub fn convert_shape_to_path(&mut self) -> bool {
+        let geometry_attrs: &[AttributeId] = match self.tag_id() {
+            Some(ElementId::Rect) => &[AttributeId::X, AttributeId::Y, AttributeId::Width,
+                                       AttributeId::Height, AttributeId::Rx, AttributeId::Ry],
+            Some(ElementId::Circle) => &[AttributeId::Cx, AttributeId::Cy, AttributeId::R],
+            Some(ElementId::Ellipse) => &[AttributeId::Cx, AttributeId::Cy,
+                                          AttributeId::Rx, AttributeId::Ry],
+            Some(ElementId::Line) => &[AttributeId::X1, AttributeId::Y1,
+                                       AttributeId::X2, AttributeId::Y2],
+            Some(ElementId::Polyline) | Some(ElementId::Polygon) => &[AttributeId::Points],
+            _ => return false,
+        };
+
+        let path = match self.shape_to_path() {
+            Some(path) => path,
+            None => return false,
+        };
+
+        self.set_tag_name(ElementId::Path);
+        self.remove_attributes(geometry_attrs);
+        self.set_attribute((AttributeId::D, path));
+
+        true
+    }
+
+    /// Calculates the element's bounding box in its user space.
+    ///
+    /// Supports shapes, `g`, `a`, `switch`, `svg`, `symbol` and `use` elements.
+    /// Containers accumulate the bounding boxes of their children
+    /// through the children's `transform` attributes.
+    /// Nested `svg` elements are mapped through their `x`, `y` and `viewBox`.
+    /// A `symbol` is taken into account only when it's referenced by a `use`.
+    /// `svg` and `symbol` elements referenced by a `use` are mapped through their `viewBox`
+    /// using the `use` element's `width` and `height` when they are set.
+    /// Conditional attributes are not evaluated, so only the first child element
+    /// of a `switch` is taken into account.
+    /// The element's own `transform` is not applied.
+    ///
+    /// Lengths are converted into user units via [`conversion_context`].
+    ///
+    /// Returns `None` for unsupported elements, empty containers, invalid shapes
+    /// and `use` elements with cyclic references.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node or any of its descendants is currently mutably borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, ElementId, Rect};
+    ///
+    /// let doc = Document::from_str("
+    /// <svg>
+    ///     <g>
+    ///         <rect width='10' height='20'/>
+    ///         <circle cx='50' cy='50' r='10' transform='translate(10)'/>
+    ///     </g>
+    /// </svg>").unwrap();
+    /// let g = doc.descendants().find(|n| n.is_tag_name(ElementId::G)).unwrap();
+    /// assert_eq!(g.bbox(), Some(Rect::new(0.0, 0.0, 70.0, 60.0)));
+    /// ```
+    ///
+    /// [`conversion_context`]: #method.conversion_context
+    pub fn bbox(&self) -> Option<Rect> {
+        calc_bbox(self, &Transform::default(), false, &mut Vec::new()).unwrap_or(None)
+    }
+
+    /// Calculates the element's bounding box in its user space, including the stroke.
+    ///
+    /// The same as [`bbox`], but every stroked shape is expanded by half of its `stroke-width`.
+    /// Line joins and caps are not taken into account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node or any of its descendants or ancestors is currently mutably borrowed.
+    ///
+    /// [`bbox`]: #method.bbox
+    pub fn bbox_with_stroke(&self) -> Option<Rect> {
+        calc_bbox(self, &Transform::default(), true, &mut Vec::new()).unwrap_or(None)
+    }
+
+    /// Attaches a user data to the node.
+    ///
+    /// A node can store only one value of each type.
+    /// The previous value of the same type will be replaced.
+    ///
+    /// User data is not a part of the SVG, so it's ignored by the writer
+    /// and document observers. It's also not copied by [`make_copy`],
+    /// so it can be used to store non-cloneable values, like handles.
+    /// Use [`copy_user_data`] to copy a cloneable value explicitly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is currently borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, ElementId};
+    ///
+    /// #[derive(PartialEq, Debug)]
+    /// struct LayerId(u32);
+    ///
+    /// let mut doc = Document::new();
+    /// let mut rect = doc.create_element(ElementId::Rect);
+    ///
+    /// rect.set_user_data(LayerId(5));
+    /// assert_eq!(*rect.user_data::<LayerId>().unwrap(), LayerId(5));
+    /// assert!(rect.user_data::<u32>().is_none());
+    ///
+    /// doc.append(&rect);
+    /// assert_eq!(doc.to_string(), "<rect/>\n");
+    /// ```
+    ///
+    /// [`make_copy`]: #method.make_copy
+    /// [`copy_user_data`]: #method.copy_user_data
+    pub fn set_user_data<T: Any>(&mut self, value: T) {
+        self.0.borrow_mut().user_data.insert(value);
+    }
+
+    /// Returns a reference to the user data of the specified type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is currently mutably borrowed.
+    pub fn user_data<'a, T: Any>(&'a self) -> Option<Ref<'a, T>> {
+        let b = self.0.borrow();
+        if !b.user_data.contains::<T>() {
+            return None;
+        }
+
+        Some(Ref::map(b, |n| n.user_data.get::<T>().unwrap()))
+    }
+
+    /// Returns a mutable reference to the user data of the specified type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is currently borrowed.
+    pub fn user_data_mut<'a, T: Any>(&'a mut self) -> Option<RefMut<'a, T>> {
+        let b = self.0.borrow_mut();
+        if !b.user_data.contains::<T>() {
+            return None;
+        }
+
+        Some(RefMut::map(b, |n| n.user_data.get_mut::<T>().unwrap()))
+    }
+
+    /// Removes the user data of the specified type and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is currently borrowed.
+    pub fn remove_user_data<T: Any>(&mut self) -> Option<T> {
+        self.0.borrow_mut().user_data.remove::<T>()
+    }
+
+    /// Copies the user data of the specified type from the `node`.
+    ///
+    /// The existing value of the same type will be replaced.
+    /// Does nothing if the `node` doesn't have a value of this type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is currently borrowed or the `node` is currently mutably borrowed.
+    pub fn copy_user_data<T: Any + Clone>(&mut self, node: &Node) {
+        if *self == *node {
+            return;
+        }
+
+        let value = match node.user_data::<T>() {
+            Some(v) => v.clone(),
+            None => return,
+        };
+
+        self.set_user_data(value);
+    }
 }
 
 /// Cloning a `Node` only increments a reference count. It does not copy the data.
@@ -1088,6 +1656,16 @@ impl PartialEq for Node {
     }
 }
 
+impl Eq for Node {}
+
+/// Hashing a `Node` uses an address of the node data, just like the `PartialEq` does.
+impl Hash for Node {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let ptr: *const RefCell<NodeData> = &*self.0;
+        ptr.hash(state);
+    }
+}
+
 // TODO: move to Rc::ptr_eq (since 1.17) when we drop 1.13 version support
 fn same_rc<T>(a: &Rc<T>, b: &Rc<T>) -> bool {
     let a: *const T = &**a;
@@ -1095,6 +1673,403 @@ fn same_rc<T>(a: &Rc<T>, b: &Rc<T>) -> bool {
     a == b
 }
 
+/// Returns a viewport size attribute value in user units.
+fn viewport_size(attrs: &Attributes, id: AttributeId) -> Option<f64> {
+    match attrs.get_value(id) {
+        Some(&AttributeValue::Length(l)) => {
+            if l.unit == LengthUnit::None || l.unit == LengthUnit::Px {
+                Some(l.num)
+            } else {
+                None
+            }
+        }
+        Some(&AttributeValue::Number(n)) => Some(n),
+        // The default value of 'markerWidth' and 'markerHeight' is 3.
+        None if id == AttributeId::MarkerWidth || id == AttributeId::MarkerHeight => Some(3.0),
+        _ => None,
+    }
+}
+
+// The size of the viewport established by the 'svg' element.
+fn svg_viewport(svg: &Node) -> (f64, f64) {
+    if let Some(&AttributeValue::ViewBox(r)) = svg.attributes().get_value(AttributeId::ViewBox) {
+        return (r.width, r.height);
+    }
+
+    let size = |id: AttributeId| {
+        // The default value of 'width' and 'height' is 100%.
+        let len = match svg.attributes().get_value(id) {
+            Some(&AttributeValue::Length(l)) => l,
+            Some(&AttributeValue::Number(n)) => Length::new_number(n),
+            _ => Length::new(100.0, LengthUnit::Percent),
+        };
+
+        len.to_user_units(&svg.conversion_context(id))
+    };
+
+    (size(AttributeId::Width), size(AttributeId::Height))
+}
+
+// The initial 'font-size' value, aka 'medium'.
+const DEFAULT_FONT_SIZE: f64 = 16.0;
+
+fn resolve_font_size(node: &Node) -> f64 {
+    let parent_size = match node.parent() {
+        Some(parent) => resolve_font_size(&parent),
+        None => DEFAULT_FONT_SIZE,
+    };
+
+    match node.attributes().get_value(AttributeId::FontSize) {
+        Some(&AttributeValue::Length(l)) => {
+            let ctx = ConversionContext {
+                direction: LengthDirection::FontSize,
+                font_size: parent_size,
+                ..ConversionContext::default()
+            };
+
+            l.to_user_units(&ctx)
+        }
+        Some(&AttributeValue::Number(n)) => n,
+        Some(&AttributeValue::PredefValue(v)) => {
+            // Based on the CSS 2 scaling factor of 1.2.
+            match v {
+                ValueId::XxSmall => 9.0,
+                ValueId::XSmall => 10.0,
+                ValueId::Small => 13.0,
+                ValueId::Medium => DEFAULT_FONT_SIZE,
+                ValueId::Large => 18.0,
+                ValueId::XLarge => 24.0,
+                ValueId::XxLarge => 32.0,
+                ValueId::Larger => parent_size * 1.2,
+                ValueId::Smaller => parent_size / 1.2,
+                _ => parent_size,
+            }
+        }
+        _ => parent_size,
+    }
+}
+
+fn length_direction(id: AttributeId) -> LengthDirection {
+    match id {
+          AttributeId::Cx
+        | AttributeId::Dx
+        | AttributeId::Fx
+        | AttributeId::MarkerWidth
+        | AttributeId::RefX
+        | AttributeId::Rx
+        | AttributeId::Width
+        | AttributeId::X
+        | AttributeId::X1
+        | AttributeId::X2 => LengthDirection::Horizontal,
+          AttributeId::Cy
+        | AttributeId::Dy
+        | AttributeId::Fy
+        | AttributeId::Height
+        | AttributeId::MarkerHeight
+        | AttributeId::RefY
+        | AttributeId::Ry
+        | AttributeId::Y
+        | AttributeId::Y1
+        | AttributeId::Y2 => LengthDirection::Vertical,
+        AttributeId::FontSize => LengthDirection::FontSize,
+        _ => LengthDirection::Diagonal,
+    }
+}
+
+// Calculates a bounding box of the node in the coordinate system defined by `ts`.
+//
+// `links` contains elements referenced by the `use` elements that are being processed.
+// Returns an error on a cyclic reference.
+fn calc_bbox(node: &Node, ts: &Transform, with_stroke: bool, links: &mut Vec<Node>)
+    -> Result<Option<Rect>>
+{
+    let id = match node.tag_id() {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+
+    match id {
+          ElementId::G
+        | ElementId::A
+        | ElementId::Switch
+        | ElementId::Svg
+        | ElementId::Symbol => {
+            let mut bbox: Option<Rect> = None;
+            for child in node.children() {
+                // Symbols are rendered only via 'use'.
+                if child.is_tag_name(ElementId::Symbol) {
+                    continue;
+                }
+
+                let mut child_ts = *ts;
+                if child.is_tag_name(ElementId::Svg) {
+                    child_ts.append(&viewport_transform(&child, None));
+                } else {
+                    child_ts.append(&node_transform(&child));
+                }
+
+                if let Some(r) = calc_bbox(&child, &child_ts, with_stroke, links)? {
+                    bbox = Some(match bbox {
+                        Some(bbox) => bbox.union(&r),
+                        None => r,
+                    });
+                }
+
+                // Conditional attributes are not evaluated,
+                // so 'switch' renders only its first child element.
+                if id == ElementId::Switch && child.node_type() == NodeType::Element {
+                    break;
+                }
+            }
+
+            Ok(bbox)
+        }
+        ElementId::Use => {
+            let link = {
+                let attrs = node.attributes();
+                let href = attrs.get_value(AttributeId::XlinkHref);
+                match href.or_else(|| attrs.get_value(AttributeId::Href)) {
+                    Some(&AttributeValue::Link(ref link)) => link.clone(),
+                    _ => return Ok(None),
+                }
+            };
+
+            if let Some(idx) = links.iter().position(|n| *n == link) {
+                let mut ids: Vec<String> = links[idx..].iter().map(|n| n.id().clone()).collect();
+                ids.push(link.id().clone());
+                return Err(ErrorKind::ReferenceCycle(ids.join(" -> ")).into());
+            }
+
+            let x = length_attr(node, AttributeId::X).unwrap_or(0.0);
+            let y = length_attr(node, AttributeId::Y).unwrap_or(0.0);
+
+            let mut link_ts = *ts;
+            link_ts.translate(x, y);
+            match link.tag_id() {
+                Some(ElementId::Svg) | Some(ElementId::Symbol) => {
+                    link_ts.append(&viewport_transform(&link, Some(node)));
+                }
+                _ => link_ts.append(&node_transform(&link)),
+            }
+
+            links.push(link.clone());
+            let bbox = calc_bbox(&link, &link_ts, with_stroke, links);
+            links.pop();
+            bbox
+        }
+        _ => {
+            let path = match node.shape_to_path() {
+                Some(path) => path,
+                None => return Ok(None),
+            };
+
+            let bbox = match path.bbox_with_transform(ts) {
+                Some(bbox) => bbox,
+                None => return Ok(None),
+            };
+
+            if !with_stroke {
+                return Ok(Some(bbox));
+            }
+
+            let sw = stroke_width(node);
+            if sw <= 0.0 {
+                return Ok(Some(bbox));
+            }
+
+            // Half of the stroke width in the target coordinate system.
+            let hw = sw / 2.0 * (ts.a * ts.a + ts.c * ts.c).sqrt();
+            let hh = sw / 2.0 * (ts.b * ts.b + ts.d * ts.d).sqrt();
+            let rect = Rect::new(bbox.x - hw, bbox.y - hh, bbox.width + hw * 2.0, bbox.height + hh * 2.0);
+            Ok(Some(rect))
+        }
+    }
+}
+
+// Returns a transform from the nested 'svg' or 'symbol' element coordinates
+// to its parent coordinates.
+//
+// The `width` and `height` of the referencing 'use' element override the element's own.
+fn viewport_transform(elem: &Node, use_node: Option<&Node>) -> Transform {
+    let x = length_attr(elem, AttributeId::X).unwrap_or(0.0);
+    let y = length_attr(elem, AttributeId::Y).unwrap_or(0.0);
+
+    let mut ts = Transform::new(1.0, 0.0, 0.0, 1.0, x, y);
+
+    let attrs = elem.attributes();
+    let view_box = match attrs.get_value(AttributeId::ViewBox) {
+        Some(&AttributeValue::ViewBox(r)) => r,
+        _ => return ts,
+    };
+
+    let size = |id: AttributeId| {
+        use_node.and_then(|n| length_attr(n, id)).or_else(|| viewport_size(&attrs, id))
+    };
+
+    if let (Some(w), Some(h)) = (size(AttributeId::Width), size(AttributeId::Height)) {
+        if let Some(vb_ts) = aspect_ratio(&attrs).view_box_transform(view_box, w, h) {
+            ts.append(&vb_ts);
+        }
+    }
+
+    ts
+}
+
+pub fn node_transform(node: &Node) -> Transform {
+    match node.attributes().get_value(AttributeId::Transform) {
+        Some(&AttributeValue::Transform(ts)) => ts,
+        Some(&AttributeValue::TransformList(ref list)) => list.to_transform(),
+        _ => Transform::default(),
+    }
+}
+
+// Returns a length attribute value in user units.
+fn length_attr(node: &Node, id: AttributeId) -> Option<f64> {
+    let len = match node.attributes().get_value(id) {
+        Some(&AttributeValue::Length(l)) => l,
+        Some(&AttributeValue::Number(n)) => return Some(n),
+        _ => return None,
+    };
+
+    Some(len.to_user_units(&node.conversion_context(id)))
+}
+
+// Returns a resolved 'stroke-width' or 0 when the shape has no stroke.
+fn stroke_width(node: &Node) -> f64 {
+    // The default value of 'stroke' is 'none'.
+    let mut has_stroke = false;
+    for n in node.parents_with_self() {
+        match n.attributes().get_value(AttributeId::Stroke) {
+            None | Some(&AttributeValue::PredefValue(ValueId::Inherit)) => continue,
+            Some(&AttributeValue::PredefValue(ValueId::None)) => {}
+            Some(_) => has_stroke = true,
+        }
+
+        break;
+    }
+
+    if !has_stroke {
+        return 0.0;
+    }
+
+    for n in node.parents_with_self() {
+        if let Some(sw) = length_attr(&n, AttributeId::StrokeWidth) {
+            return sw;
+        }
+    }
+
+    // The default value of 'stroke-width' is 1.
+    1.0
+}
+
+fn shape_to_path(node: &Node) -> Option<path::Path> {
+    let id = try_opt!(node.tag_id());
+
+    let attr = |id: AttributeId| length_attr(node, id).unwrap_or(0.0);
+
+    let path = match id {
+        ElementId::Path => {
+            match node.attributes().get_value(AttributeId::D) {
+                Some(&AttributeValue::Path(ref path)) => path.clone(),
+                _ => return None,
+            }
+        }
+        ElementId::Rect => {
+            let x = attr(AttributeId::X);
+            let y = attr(AttributeId::Y);
+            let w = attr(AttributeId::Width);
+            let h = attr(AttributeId::Height);
+
+            if w <= 0.0 || h <= 0.0 {
+                return None;
+            }
+
+            // If only one of the radii is set, then the other one is the same.
+            let rx = length_attr(node, AttributeId::Rx).map(|n| n.max(0.0));
+            let ry = length_attr(node, AttributeId::Ry).map(|n| n.max(0.0));
+            let (rx, ry) = match (rx, ry) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(rx), None) => (rx, rx),
+                (None, Some(ry)) => (ry, ry),
+                (None, None) => (0.0, 0.0),
+            };
+
+            let rx = rx.min(w / 2.0);
+            let ry = ry.min(h / 2.0);
+
+            if rx > 0.0 && ry > 0.0 {
+                path::Builder::new()
+                    .move_to(x + rx, y)
+                    .hline_to(x + w - rx)
+                    .arc_to(rx, ry, 0.0, false, true, x + w, y + ry)
+                    .vline_to(y + h - ry)
+                    .arc_to(rx, ry, 0.0, false, true, x + w - rx, y + h)
+                    .hline_to(x + rx)
+                    .arc_to(rx, ry, 0.0, false, true, x, y + h - ry)
+                    .vline_to(y + ry)
+                    .arc_to(rx, ry, 0.0, false, true, x + rx, y)
+                    .close_path()
+                    .finalize()
+            } else {
+                path::Builder::new()
+                    .move_to(x, y)
+                    .hline_to(x + w)
+                    .vline_to(y + h)
+                    .hline_to(x)
+                    .close_path()
+                    .finalize()
+            }
+        }
+        ElementId::Circle | ElementId::Ellipse => {
+            let cx = attr(AttributeId::Cx);
+            let cy = attr(AttributeId::Cy);
+            let (rx, ry) = if id == ElementId::Circle {
+                let r = attr(AttributeId::R);
+                (r, r)
+            } else {
+                (attr(AttributeId::Rx), attr(AttributeId::Ry))
+            };
+
+            if rx <= 0.0 || ry <= 0.0 {
+                return None;
+            }
+
+            path::Builder::new()
+                .move_to(cx + rx, cy)
+                .arc_to(rx, ry, 0.0, false, true, cx, cy + ry)
+                .arc_to(rx, ry, 0.0, false, true, cx - rx, cy)
+                .arc_to(rx, ry, 0.0, false, true, cx, cy - ry)
+                .arc_to(rx, ry, 0.0, false, true, cx + rx, cy)
+                .close_path()
+                .finalize()
+        }
+        ElementId::Line => {
+            path::Builder::new()
+                .move_to(attr(AttributeId::X1), attr(AttributeId::Y1))
+                .line_to(attr(AttributeId::X2), attr(AttributeId::Y2))
+                .finalize()
+        }
+        ElementId::Polyline | ElementId::Polygon => {
+            match node.attributes().get_value(AttributeId::Points) {
+                Some(&AttributeValue::Points(ref points)) if points.len() >= 2 => {
+                    points.to_path(id == ElementId::Polygon)
+                }
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    Some(path)
+}
+
+fn aspect_ratio(attrs: &Attributes) -> AspectRatio {
+    match attrs.get_value(AttributeId::PreserveAspectRatio) {
+        Some(&AttributeValue::AspectRatio(a)) => a,
+        _ => AspectRatio::default(),
+    }
+}
+
 impl fmt::Debug for Node {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.node_type() {
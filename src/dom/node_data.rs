@@ -14,6 +14,8 @@ use {
     NodeType,
     Node,
 };
+use super::observer::ObserversLink;
+use super::user_data::UserData;
 
 pub type Link = Rc<RefCell<NodeData>>;
 pub type WeakLink = Weak<RefCell<NodeData>>;
@@ -34,6 +36,10 @@ pub struct NodeData {
     pub attributes: Attributes,
     pub linked_nodes: Vec<WeakLink>,
     pub text: String,
+    pub user_data: UserData,
+
+    /// Document observers. Used only by the root node.
+    pub observers: Option<ObserversLink>,
 }
 
 impl NodeData {
@@ -0,0 +1,138 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use {
+    Attribute,
+    AttributeName,
+    Node,
+};
+
+/// A document modification reported to the observers.
+///
+/// All variants contain the modified node and its state before the modification.
+///
+/// See [`Document::add_observer`] for details.
+///
+/// [`Document::add_observer`]: struct.Document.html#method.add_observer
+#[derive(Clone,Debug)]
+pub enum Mutation {
+    /// A node was inserted into the tree by `append`, `prepend`, `insert_before`
+    /// or `insert_after`.
+    ///
+    /// `old_parent` and `old_next_sibling` point to the previous position of the node,
+    /// if it was already a part of the tree.
+    Insert {
+        /// An inserted node.
+        node: Node,
+        /// A previous parent node.
+        old_parent: Option<Node>,
+        /// A previous next sibling node.
+        old_next_sibling: Option<Node>,
+    },
+    /// A node was detached from its parent by `detach` or `remove`.
+    Detach {
+        /// A detached node.
+        node: Node,
+        /// A previous parent node.
+        old_parent: Node,
+        /// A previous next sibling node.
+        old_next_sibling: Option<Node>,
+    },
+    /// An attribute was inserted, replaced or removed.
+    Attribute {
+        /// A modified node.
+        node: Node,
+        /// An attribute name.
+        name: AttributeName,
+        /// A previous attribute, or `None` if the attribute was just inserted.
+        old_attribute: Option<Attribute>,
+    },
+    /// A text data was changed by `set_text`.
+    Text {
+        /// A modified node.
+        node: Node,
+        /// A previous text.
+        old_value: String,
+    },
+    /// An ID was changed by `set_id`.
+    Id {
+        /// A modified node.
+        node: Node,
+        /// A previous ID.
+        old_value: String,
+    },
+}
+
+impl Mutation {
+    /// Returns a modified node.
+    pub fn node(&self) -> &Node {
+        match *self {
+              Mutation::Insert { ref node, .. }
+            | Mutation::Detach { ref node, .. }
+            | Mutation::Attribute { ref node, .. }
+            | Mutation::Text { ref node, .. }
+            | Mutation::Id { ref node, .. } => node,
+        }
+    }
+}
+
+/// An observer identifier returned by [`Document::add_observer`].
+///
+/// [`Document::add_observer`]: struct.Document.html#method.add_observer
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub struct ObserverId(usize);
+
+pub type ObserversLink = Rc<RefCell<Observers>>;
+// `dyn` is not used, because it requires Rust 1.27.
+#[allow(unknown_lints, bare_trait_objects)]
+type Callback = Box<FnMut(&Mutation)>;
+
+/// A list of the document observers.
+///
+/// Stored by the root node.
+pub struct Observers {
+    next_id: usize,
+    list: Vec<(ObserverId, Callback)>,
+}
+
+impl Observers {
+    pub fn new() -> Observers {
+        Observers {
+            next_id: 0,
+            list: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, f: Callback) -> ObserverId {
+        let id = ObserverId(self.next_id);
+        self.next_id += 1;
+        self.list.push((id, f));
+        id
+    }
+
+    /// Returns `false` if an observer with such `id` was not found.
+    pub fn remove(&mut self, id: ObserverId) -> bool {
+        let len = self.list.len();
+        self.list.retain(|v| v.0 != id);
+        len != self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
+
+/// Passes the `mutation` to all observers.
+///
+/// Modifications made by an observer itself are not reported.
+pub fn notify(observers: &ObserversLink, mutation: Mutation) {
+    if let Ok(mut observers) = observers.try_borrow_mut() {
+        for item in &mut observers.list {
+            (item.1)(&mutation);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use error::Result;
+use {
+    AttributeId,
+    AttributeValue,
+    Document,
+    ElementId,
+    ErrorKind,
+    Node,
+    NodeType,
+};
+
+/// A reference between two elements.
+///
+/// Created by an attribute with a `Link` or `FuncLink` value.
+#[derive(Clone,Debug)]
+pub struct Reference {
+    /// An element with a link attribute.
+    pub source: Node,
+    /// A link attribute.
+    pub attribute: AttributeId,
+    /// A referenced element.
+    pub target: Node,
+}
+
+/// A document-level graph of references between elements.
+///
+/// Unlike [`Node::linked_nodes`], which provides only one-hop information,
+/// this graph can be used to resolve transitive dependencies and detect reference cycles.
+///
+/// Referencing an element means referencing its children too. So a `use` element,
+/// which references its own ancestor, creates a cycle.
+///
+/// References from `a`, `script` and animation elements are not rendering dependencies,
+/// so they are listed in [`edges`], but ignored by other methods.
+///
+/// The graph is a snapshot. It will not be updated after the document modification.
+///
+/// [`Node::linked_nodes`]: struct.Node.html#method.linked_nodes
+/// [`edges`]: #method.edges
+pub struct ReferenceGraph {
+    /// All elements in the document order.
+    nodes: Vec<Node>,
+    /// All references in the document order.
+    edges: Vec<Reference>,
+    /// Indexes of the dependency edges grouped by the source node.
+    deps: HashMap<Node, Vec<usize>>,
+}
+
+impl ReferenceGraph {
+    /// Constructs a new `ReferenceGraph` from the `Document`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the nodes are currently mutability borrowed.
+    pub fn new(doc: &Document) -> ReferenceGraph {
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let mut deps: HashMap<Node, Vec<usize>> = HashMap::new();
+
+        for node in doc.descendants().filter(|n| n.node_type() == NodeType::Element) {
+            let is_dep = is_dependency_source(&node);
+
+            for (aid, attr) in node.attributes().iter_svg() {
+                match attr.value {
+                      AttributeValue::Link(ref link)
+                    | AttributeValue::FuncLink(ref link) => {
+                        if is_dep {
+                            deps.entry(node.clone()).or_insert_with(Vec::new).push(edges.len());
+                        }
+
+                        edges.push(Reference {
+                            source: node.clone(),
+                            attribute: aid,
+                            target: link.clone(),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+
+            nodes.push(node);
+        }
+
+        ReferenceGraph {
+            nodes: nodes,
+            edges: edges,
+            deps: deps,
+        }
+    }
+
+    /// Returns all references in the document order.
+    pub fn edges(&self) -> &[Reference] {
+        &self.edges
+    }
+
+    /// Returns references created by the attributes of the `node`.
+    pub fn references(&self, node: &Node) -> Vec<&Reference> {
+        self.edges.iter().filter(|r| r.source == *node).collect()
+    }
+
+    /// Returns all elements that the `node` depends on.
+    ///
+    /// Includes elements referenced by the `node` and its descendants,
+    /// and then, recursively, by referenced elements and their descendants.
+    ///
+    /// Elements are listed in the order of discovery.
+    /// The `node` itself will be listed only when it is a part of a reference cycle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the nodes are currently mutability borrowed.
+    pub fn dependencies(&self, node: &Node) -> Vec<Node> {
+        let mut list = Vec::new();
+        let mut visited = HashSet::new();
+        let mut queue = vec![node.clone()];
+
+        while let Some(n) = queue.pop() {
+            for d in n.descendants() {
+                for target in self.dependency_targets(&d) {
+                    if visited.insert(target.clone()) {
+                        list.push(target.clone());
+                        queue.push(target.clone());
+                    }
+                }
+            }
+        }
+
+        list
+    }
+
+    /// Returns children of all `defs` elements in the topological order.
+    ///
+    /// Each element will be placed after all elements it depends on.
+    /// Otherwise, the document order is preserved.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReferenceCycle`] if dependencies contain a cycle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the nodes are currently mutability borrowed.
+    ///
+    /// [`ReferenceCycle`]: enum.ErrorKind.html
+    pub fn defs_order(&self) -> Result<Vec<Node>> {
+        let list: Vec<Node> = self.nodes.iter()
+            .filter(|n| n.is_tag_name(ElementId::Defs))
+            .flat_map(|n| n.children())
+            .filter(|n| n.node_type() == NodeType::Element)
+            .collect();
+
+        let deps: Vec<Vec<Node>> = list.iter()
+            .map(|n| self.dependencies(n).into_iter().filter(|d| list.contains(d)).collect())
+            .collect();
+
+        let mut ordered: Vec<Node> = Vec::with_capacity(list.len());
+        while ordered.len() != list.len() {
+            let idx = (0..list.len()).find(|&i| {
+                !ordered.contains(&list[i]) && deps[i].iter().all(|d| ordered.contains(d))
+            });
+
+            match idx {
+                Some(i) => ordered.push(list[i].clone()),
+                None => {
+                    // An unresolved dependency is always a cycle.
+                    self.check_cycles()?;
+                    break;
+                }
+            }
+        }
+
+        Ok(ordered)
+    }
+
+    /// Returns all detected reference cycles.
+    ///
+    /// Each cycle is a list of elements, where each element either contains
+    /// or references the next one, and the last one points to the first one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the nodes are currently mutability borrowed.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Document, ElementId};
+    ///
+    /// let doc = Document::from_str(
+    /// "<svg>
+    ///     <g id='g1'>
+    ///         <use xlink:href='#g1'/>
+    ///     </g>
+    /// </svg>").unwrap();
+    ///
+    /// let cycles = doc.reference_graph().cycles();
+    /// assert_eq!(cycles.len(), 1);
+    /// assert_eq!(cycles[0][0].is_tag_name(ElementId::G), true);
+    /// assert_eq!(cycles[0][1].is_tag_name(ElementId::Use), true);
+    /// ```
+    pub fn cycles(&self) -> Vec<Vec<Node>> {
+        #[derive(Clone,Copy,PartialEq)]
+        enum State {
+            InProgress,
+            Done,
+        }
+
+        let mut cycles = Vec::new();
+        let mut states: HashMap<Node, State> = HashMap::new();
+
+        // We are using a manual stack instead of a recursion
+        // to prevent a stack overflow on a deep documents.
+        for root in &self.nodes {
+            if states.contains_key(root) {
+                continue;
+            }
+
+            let mut stack: Vec<(Node, Vec<Node>)> = Vec::new();
+            states.insert(root.clone(), State::InProgress);
+            stack.push((root.clone(), self.successors(root)));
+
+            while !stack.is_empty() {
+                let next = stack.last_mut().unwrap().1.pop();
+                match next {
+                    Some(n) => {
+                        match states.get(&n).cloned() {
+                            None => {
+                                states.insert(n.clone(), State::InProgress);
+                                let succ = self.successors(&n);
+                                stack.push((n, succ));
+                            }
+                            Some(State::InProgress) => {
+                                let pos = stack.iter().position(|v| v.0 == n).unwrap();
+                                cycles.push(stack[pos..].iter().map(|v| v.0.clone()).collect());
+                            }
+                            Some(State::Done) => {}
+                        }
+                    }
+                    None => {
+                        let (n, _) = stack.pop().unwrap();
+                        states.insert(n, State::Done);
+                    }
+                }
+            }
+        }
+
+        cycles
+    }
+
+    /// Checks that the document doesn't have reference cycles.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReferenceCycle`] with the first detected cycle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the nodes are currently mutability borrowed.
+    ///
+    /// [`ReferenceCycle`]: enum.ErrorKind.html
+    pub fn check_cycles(&self) -> Result<()> {
+        match self.cycles().first() {
+            Some(cycle) => {
+                let mut names: Vec<String> = cycle.iter().map(node_label).collect();
+                names.push(node_label(&cycle[0]));
+                Err(ErrorKind::ReferenceCycle(names.join(" -> ")).into())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn dependency_targets(&self, node: &Node) -> Vec<&Node> {
+        match self.deps.get(node) {
+            Some(list) => list.iter().map(|i| &self.edges[*i].target).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns element's children and dependencies in reverse order.
+    fn successors(&self, node: &Node) -> Vec<Node> {
+        let mut list: Vec<Node> = node.children()
+            .filter(|n| n.node_type() == NodeType::Element)
+            .collect();
+        list.extend(self.dependency_targets(node).into_iter().cloned());
+        list.reverse();
+        list
+    }
+}
+
+/// Checks that references from this element are rendering dependencies.
+fn is_dependency_source(node: &Node) -> bool {
+    match node.tag_id() {
+        Some(ElementId::A)
+        | Some(ElementId::Animate)
+        | Some(ElementId::AnimateColor)
+        | Some(ElementId::AnimateMotion)
+        | Some(ElementId::AnimateTransform)
+        | Some(ElementId::Script)
+        | Some(ElementId::Set) => false,
+        _ => true,
+    }
+}
+
+fn node_label(node: &Node) -> String {
+    if node.has_id() {
+        format!("#{}", *node.id())
+    } else {
+        format!("{:?}", *node.tag_name().unwrap())
+    }
+}
@@ -0,0 +1,159 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use {
+    AttributeId,
+    AttributeValue,
+    Document,
+    ElementId,
+    Node,
+    NodeType,
+    ValueId,
+};
+use super::node::node_transform;
+
+pub fn bake_transforms(doc: &Document) {
+    // Groups are processed in the document order, so transforms of nested groups
+    // are accumulated on the way down. A nested group can receive a transform
+    // from its parent, so the transform is checked only when the group is processed.
+    let groups: Vec<Node> = doc.root().descendants()
+        .filter(|n| n.is_tag_name(ElementId::G))
+        .collect();
+
+    for mut g in groups {
+        if !g.has_attribute(AttributeId::Transform) || !can_push_transform(&g) {
+            continue;
+        }
+
+        let ts = node_transform(&g);
+        for mut child in g.children() {
+            match child.tag_id() {
+                  Some(ElementId::Title)
+                | Some(ElementId::Desc)
+                | Some(ElementId::Metadata) => continue,
+                Some(_) => {}
+                None => continue,
+            }
+
+            let mut child_ts = node_transform(&child);
+            child_ts.prepend(&ts);
+            child.set_attribute((AttributeId::Transform, child_ts));
+        }
+
+        g.remove_attribute(AttributeId::Transform);
+    }
+
+    let paths: Vec<Node> = doc.root().descendants()
+        .filter(|n| n.is_tag_name(ElementId::Path) && n.has_attribute(AttributeId::Transform))
+        .collect();
+
+    for mut node in paths {
+        if !can_bake_transform(&node) {
+            continue;
+        }
+
+        let ts = node_transform(&node);
+        let mut path = match node.attributes().get_value(AttributeId::D) {
+            Some(&AttributeValue::Path(ref path)) => path.clone(),
+            _ => continue,
+        };
+
+        path.transform(&ts);
+        node.set_attribute((AttributeId::D, path));
+        node.remove_attribute(AttributeId::Transform);
+    }
+}
+
+// A group transform can be moved to its children only when the group itself
+// doesn't depend on its user space and all children can have a transform.
+fn can_push_transform(g: &Node) -> bool {
+    for id in &[AttributeId::ClipPath, AttributeId::Mask, AttributeId::Filter] {
+        if is_func_link(g.attributes().get_value(*id)) {
+            return false;
+        }
+    }
+
+    g.children().all(|child| {
+        if child.node_type() != NodeType::Element {
+            return true;
+        }
+
+        match child.tag_id() {
+              Some(ElementId::A)
+            | Some(ElementId::Circle)
+            | Some(ElementId::Desc)
+            | Some(ElementId::Ellipse)
+            | Some(ElementId::G)
+            | Some(ElementId::Image)
+            | Some(ElementId::Line)
+            | Some(ElementId::Metadata)
+            | Some(ElementId::Path)
+            | Some(ElementId::Polygon)
+            | Some(ElementId::Polyline)
+            | Some(ElementId::Rect)
+            | Some(ElementId::Switch)
+            | Some(ElementId::Text)
+            | Some(ElementId::Title)
+            | Some(ElementId::Use) => true,
+            _ => false,
+        }
+    })
+}
+
+// A transform can be applied to the path data only when it doesn't affect anything else,
+// like paint servers, markers or a stroke width.
+fn can_bake_transform(node: &Node) -> bool {
+    // Animations are defined in the current user space.
+    if node.children().any(|n| n.node_type() == NodeType::Element) {
+        return false;
+    }
+
+    for id in &[AttributeId::ClipPath, AttributeId::Mask, AttributeId::Filter] {
+        if is_func_link(node.attributes().get_value(*id)) {
+            return false;
+        }
+    }
+
+    for id in &[AttributeId::Fill, AttributeId::Stroke, AttributeId::Marker,
+                AttributeId::MarkerStart, AttributeId::MarkerMid, AttributeId::MarkerEnd] {
+        if is_func_link(resolve_attribute(node, *id).as_ref()) {
+            return false;
+        }
+    }
+
+    // The default value of 'stroke' is 'none'.
+    let has_stroke = match resolve_attribute(node, AttributeId::Stroke) {
+        None | Some(AttributeValue::PredefValue(ValueId::None)) => false,
+        Some(_) => true,
+    };
+
+    if has_stroke {
+        // A stroke is not affected only by a translate and a rotate.
+        let ts = node_transform(node);
+        if ts.has_scale() || ts.has_skew() {
+            return false;
+        }
+    }
+
+    true
+}
+
+// Returns the value of an inherited attribute.
+fn resolve_attribute(node: &Node, id: AttributeId) -> Option<AttributeValue> {
+    for n in node.parents_with_self() {
+        match n.attributes().get_value(id) {
+            None | Some(&AttributeValue::PredefValue(ValueId::Inherit)) => continue,
+            Some(v) => return Some(v.clone()),
+        }
+    }
+
+    None
+}
+
+fn is_func_link(value: Option<&AttributeValue>) -> bool {
+    match value {
+        Some(&AttributeValue::FuncLink(_)) => true,
+        _ => false,
+    }
+}
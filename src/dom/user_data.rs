@@ -0,0 +1,68 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// `dyn` is not used, because it requires Rust 1.27.
+#![allow(unknown_lints, bare_trait_objects)]
+
+use std::any::{
+    Any,
+    TypeId,
+};
+
+struct Slot {
+    type_id: TypeId,
+    value: Box<Any>,
+}
+
+/// A typed storage of the user data.
+///
+/// Can store only one value of each type.
+pub struct UserData(Vec<Slot>);
+
+impl UserData {
+    pub fn new() -> UserData {
+        UserData(Vec::new())
+    }
+
+    pub fn contains<T: Any>(&self) -> bool {
+        self.position::<T>().is_some()
+    }
+
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        match self.position::<T>() {
+            Some(idx) => self.0[idx].value.downcast_ref::<T>(),
+            None => None,
+        }
+    }
+
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        match self.position::<T>() {
+            Some(idx) => self.0[idx].value.downcast_mut::<T>(),
+            None => None,
+        }
+    }
+
+    pub fn insert<T: Any>(&mut self, value: T) {
+        self.remove::<T>();
+        self.0.push(Slot {
+            type_id: TypeId::of::<T>(),
+            value: Box::new(value),
+        });
+    }
+
+    pub fn remove<T: Any>(&mut self) -> Option<T> {
+        match self.position::<T>() {
+            Some(idx) => {
+                let slot = self.0.remove(idx);
+                slot.value.downcast::<T>().ok().map(|v| *v)
+            }
+            None => None,
+        }
+    }
+
+    fn position<T: Any>(&self) -> Option<usize> {
+        let id = TypeId::of::<T>();
+        self.0.iter().position(|s| s.type_id == id)
+    }
+}
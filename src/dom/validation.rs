@@ -0,0 +1,555 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use {
+    AttributeId,
+    AttributeType,
+    AttributeValue,
+    Document,
+    ElementId,
+    ElementType,
+    LengthUnit,
+    Name,
+    Node,
+    NodeType,
+};
+
+/// A violation detected by [`Document::validate`].
+///
+/// [`Document::validate`]: struct.Document.html#method.validate
+#[derive(Clone,Debug)]
+pub struct Violation {
+    /// An offending node.
+    pub node: Node,
+    /// A violation kind.
+    pub kind: ViolationKind,
+}
+
+/// A kind of the [`Violation`].
+///
+/// [`Violation`]: struct.Violation.html
+#[derive(Clone,Copy,PartialEq,Debug)]
+pub enum ViolationKind {
+    /// A node is not allowed as a child of its parent.
+    InvalidChild,
+    /// A required attribute is missing.
+    MissingAttribute(AttributeId),
+    /// An attribute is not applicable to the element.
+    InvalidAttribute(AttributeId),
+    /// An attribute value is out of the allowed range.
+    InvalidValue(AttributeId),
+}
+
+pub fn validate(doc: &Document) -> Vec<Violation> {
+    let mut list = Vec::new();
+
+    for node in doc.descendants() {
+        let parent_id = match node.parent() {
+            Some(parent) => parent.tag_id(),
+            None => continue,
+        };
+
+        match node.node_type() {
+            NodeType::Element => {
+                let id = match node.tag_id() {
+                    Some(id) => id,
+                    // Non-SVG elements are not validated.
+                    None => continue,
+                };
+
+                if let Some(parent_id) = parent_id {
+                    if !is_valid_child(parent_id, id) {
+                        push(&mut list, &node, ViolationKind::InvalidChild);
+                    }
+                }
+
+                for aid in required_attributes(id) {
+                    // SVG 2 allows `href` instead of `xlink:href`.
+                    let has_href =    *aid == AttributeId::XlinkHref
+                                   && node.has_attribute(AttributeId::Href);
+
+                    if !node.has_attribute(*aid) && !has_href {
+                        push(&mut list, &node, ViolationKind::MissingAttribute(*aid));
+                    }
+                }
+
+                validate_attributes(&node, id, &mut list);
+            }
+            NodeType::Text | NodeType::Cdata => {
+                if let Some(parent_id) = parent_id {
+                    if !is_text_allowed(parent_id) && !node.text().trim().is_empty() {
+                        push(&mut list, &node, ViolationKind::InvalidChild);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    list
+}
+
+fn push(list: &mut Vec<Violation>, node: &Node, kind: ViolationKind) {
+    list.push(Violation {
+        node: node.clone(),
+        kind: kind,
+    });
+}
+
+fn validate_attributes(node: &Node, id: ElementId, list: &mut Vec<Violation>) {
+    let attrs = node.attributes();
+    for attr in attrs.iter() {
+        let aid = match attr.name {
+            Name::Id(aid) => aid,
+            // Non-SVG attributes are not validated.
+            Name::Name(_) => continue,
+        };
+
+        if !is_applicable(id, aid) {
+            push(list, node, ViolationKind::InvalidAttribute(aid));
+        } else if !is_valid_value(id, aid, &attr.value) {
+            push(list, node, ViolationKind::InvalidValue(aid));
+        }
+    }
+}
+
+fn is_animation(id: ElementId) -> bool {
+    match id {
+          ElementId::Animate
+        | ElementId::AnimateColor
+        | ElementId::AnimateMotion
+        | ElementId::AnimateTransform
+        | ElementId::Set => true,
+        _ => false,
+    }
+}
+
+fn is_descriptive(id: ElementId) -> bool {
+    match id {
+          ElementId::Desc
+        | ElementId::Metadata
+        | ElementId::Title => true,
+        _ => false,
+    }
+}
+
+fn is_filter_primitive(id: ElementId) -> bool {
+    match id {
+          ElementId::FeBlend
+        | ElementId::FeColorMatrix
+        | ElementId::FeComponentTransfer
+        | ElementId::FeComposite
+        | ElementId::FeConvolveMatrix
+        | ElementId::FeDiffuseLighting
+        | ElementId::FeDisplacementMap
+        | ElementId::FeFlood
+        | ElementId::FeGaussianBlur
+        | ElementId::FeImage
+        | ElementId::FeMerge
+        | ElementId::FeMorphology
+        | ElementId::FeOffset
+        | ElementId::FeSpecularLighting
+        | ElementId::FeTile
+        | ElementId::FeTurbulence => true,
+        _ => false,
+    }
+}
+
+fn is_light_source(id: ElementId) -> bool {
+    match id {
+          ElementId::FeDistantLight
+        | ElementId::FePointLight
+        | ElementId::FeSpotLight => true,
+        _ => false,
+    }
+}
+
+/// Checks that the element can be a child of a container element.
+fn is_container_child(id: ElementId) -> bool {
+    if is_animation(id) || is_descriptive(id) || id.is_shape() {
+        return true;
+    }
+
+    match id {
+          ElementId::A
+        | ElementId::AltGlyphDef
+        | ElementId::ClipPath
+        | ElementId::ColorProfile
+        | ElementId::Cursor
+        | ElementId::Defs
+        | ElementId::Filter
+        | ElementId::Font
+        | ElementId::FontFace
+        | ElementId::ForeignObject
+        | ElementId::G
+        | ElementId::Image
+        | ElementId::LinearGradient
+        | ElementId::Marker
+        | ElementId::Mask
+        | ElementId::Pattern
+        | ElementId::RadialGradient
+        | ElementId::Script
+        | ElementId::Style
+        | ElementId::Svg
+        | ElementId::Switch
+        | ElementId::Symbol
+        | ElementId::Text
+        | ElementId::Use
+        | ElementId::View => true,
+        _ => false,
+    }
+}
+
+/// Checks the element nesting according to the SVG 1.1 content model.
+///
+/// Elements with an unrestricted or a rarely used content model are not checked.
+fn is_valid_child(parent: ElementId, child: ElementId) -> bool {
+    match parent {
+        // `switch` is a container with a restricted content model, so it's checked first.
+        ElementId::Switch => {
+            if is_animation(child) || is_descriptive(child) || child.is_shape() {
+                return true;
+            }
+
+            match child {
+                  ElementId::A
+                | ElementId::ForeignObject
+                | ElementId::G
+                | ElementId::Image
+                | ElementId::Svg
+                | ElementId::Switch
+                | ElementId::Text
+                | ElementId::Use => true,
+                _ => false,
+            }
+        }
+        _ if parent.is_container() => is_container_child(child),
+        ElementId::ClipPath => {
+               is_animation(child) || is_descriptive(child) || child.is_shape()
+            || child == ElementId::Text || child == ElementId::Use
+        }
+          ElementId::Circle
+        | ElementId::Ellipse
+        | ElementId::Image
+        | ElementId::Line
+        | ElementId::Path
+        | ElementId::Polygon
+        | ElementId::Polyline
+        | ElementId::Rect
+        | ElementId::Use => is_animation(child) || is_descriptive(child),
+        ElementId::Text => {
+            if is_animation(child) || is_descriptive(child) {
+                return true;
+            }
+
+            match child {
+                  ElementId::A
+                | ElementId::AltGlyph
+                | ElementId::TextPath
+                | ElementId::Tref
+                | ElementId::Tspan => true,
+                _ => false,
+            }
+        }
+        ElementId::Tspan | ElementId::TextPath => {
+            if is_descriptive(child) {
+                return true;
+            }
+
+            match child {
+                  ElementId::A
+                | ElementId::AltGlyph
+                | ElementId::Animate
+                | ElementId::AnimateColor
+                | ElementId::Set
+                | ElementId::Tref
+                | ElementId::Tspan => true,
+                _ => false,
+            }
+        }
+        ElementId::Tref => {
+               is_descriptive(child)
+            || child == ElementId::Animate
+            || child == ElementId::AnimateColor
+            || child == ElementId::Set
+        }
+        ElementId::AltGlyph | ElementId::Script | ElementId::Style => false,
+        ElementId::LinearGradient | ElementId::RadialGradient => {
+            if is_descriptive(child) {
+                return true;
+            }
+
+            match child {
+                  ElementId::Animate
+                | ElementId::AnimateTransform
+                | ElementId::Set
+                | ElementId::Stop => true,
+                _ => false,
+            }
+        }
+        ElementId::Stop => {
+            match child {
+                  ElementId::Animate
+                | ElementId::AnimateColor
+                | ElementId::Set => true,
+                _ => false,
+            }
+        }
+        ElementId::Filter => {
+               is_descriptive(child) || is_filter_primitive(child)
+            || child == ElementId::Animate || child == ElementId::Set
+        }
+        ElementId::FeComponentTransfer => {
+            match child {
+                  ElementId::FeFuncA
+                | ElementId::FeFuncB
+                | ElementId::FeFuncG
+                | ElementId::FeFuncR => true,
+                _ => false,
+            }
+        }
+        ElementId::FeMerge => child == ElementId::FeMergeNode,
+        ElementId::FeDiffuseLighting | ElementId::FeSpecularLighting => {
+            is_descriptive(child) || is_light_source(child)
+        }
+        ElementId::FeImage => {
+            match child {
+                  ElementId::Animate
+                | ElementId::AnimateTransform
+                | ElementId::Set => true,
+                _ => false,
+            }
+        }
+        ElementId::AnimateMotion => is_descriptive(child) || child == ElementId::Mpath,
+          ElementId::Cursor
+        | ElementId::Mpath
+        | ElementId::View => is_descriptive(child),
+        _ => {
+            if is_filter_primitive(parent)
+                || is_light_source(parent)
+                || parent == ElementId::FeFuncA
+                || parent == ElementId::FeFuncB
+                || parent == ElementId::FeFuncG
+                || parent == ElementId::FeFuncR
+                || parent == ElementId::FeMergeNode {
+                child == ElementId::Animate || child == ElementId::Set
+            } else if is_animation(parent) {
+                is_descriptive(child)
+            } else {
+                true
+            }
+        }
+    }
+}
+
+/// Checks that the element can contain a text data.
+fn is_text_allowed(id: ElementId) -> bool {
+    match id {
+          ElementId::A
+        | ElementId::AltGlyph
+        | ElementId::Desc
+        | ElementId::ForeignObject
+        | ElementId::Metadata
+        | ElementId::Script
+        | ElementId::Style
+        | ElementId::Text
+        | ElementId::TextPath
+        | ElementId::Title
+        | ElementId::Tref
+        | ElementId::Tspan => true,
+        _ => false,
+    }
+}
+
+fn required_attributes(id: ElementId) -> &'static [AttributeId] {
+    match id {
+        ElementId::Circle => &[AttributeId::R],
+        ElementId::Ellipse => &[AttributeId::Rx, AttributeId::Ry],
+        ElementId::Image => &[AttributeId::Width, AttributeId::Height, AttributeId::XlinkHref],
+        ElementId::Path => &[AttributeId::D],
+        ElementId::Polygon | ElementId::Polyline => &[AttributeId::Points],
+        ElementId::Rect => &[AttributeId::Width, AttributeId::Height],
+        ElementId::Tref | ElementId::TextPath | ElementId::Use => &[AttributeId::XlinkHref],
+          ElementId::FeBlend
+        | ElementId::FeComposite
+        | ElementId::FeDisplacementMap => &[AttributeId::In2],
+          ElementId::FeFuncA
+        | ElementId::FeFuncB
+        | ElementId::FeFuncG
+        | ElementId::FeFuncR => &[AttributeId::Type],
+        _ => &[],
+    }
+}
+
+/// Checks that the element can have presentation attributes.
+fn has_presentation_attributes(id: ElementId) -> bool {
+    if is_animation(id) || is_descriptive(id) || is_light_source(id) {
+        return false;
+    }
+
+    match id {
+          ElementId::ColorProfile
+        | ElementId::Cursor
+        | ElementId::FeFuncA
+        | ElementId::FeFuncB
+        | ElementId::FeFuncG
+        | ElementId::FeFuncR
+        | ElementId::FeMergeNode
+        | ElementId::FontFace
+        | ElementId::FontFaceFormat
+        | ElementId::FontFaceName
+        | ElementId::FontFaceSrc
+        | ElementId::FontFaceUri
+        | ElementId::Hkern
+        | ElementId::Mpath
+        | ElementId::Script
+        | ElementId::Style
+        | ElementId::View
+        | ElementId::Vkern => false,
+        _ => true,
+    }
+}
+
+/// Checks that the element can have conditional processing and graphical event attributes.
+fn is_renderable(id: ElementId) -> bool {
+    if id.is_shape() {
+        return true;
+    }
+
+    match id {
+          ElementId::A
+        | ElementId::AltGlyph
+        | ElementId::Defs
+        | ElementId::ForeignObject
+        | ElementId::G
+        | ElementId::Image
+        | ElementId::Svg
+        | ElementId::Switch
+        | ElementId::Text
+        | ElementId::TextPath
+        | ElementId::Tref
+        | ElementId::Tspan
+        | ElementId::Use => true,
+        _ => false,
+    }
+}
+
+/// Checks that the attribute is applicable to the element.
+///
+/// Only commonly used attributes are checked.
+fn is_applicable(id: ElementId, aid: AttributeId) -> bool {
+    // `fill` on animation elements is a timing attribute and not a presentation one.
+    if aid == AttributeId::Fill && is_animation(id) {
+        return true;
+    }
+
+    if aid.is_presentation() {
+        return has_presentation_attributes(id);
+    }
+
+    if aid.is_conditional_processing() {
+        return is_renderable(id) || is_animation(id)
+            || id == ElementId::ClipPath || id == ElementId::Mask || id == ElementId::Pattern;
+    }
+
+    if aid.is_graphical_event() && aid != AttributeId::Onload {
+        return is_renderable(id);
+    }
+
+    if aid.is_document_event() {
+        return id == ElementId::Svg;
+    }
+
+    let list: &[ElementId] = match aid {
+        AttributeId::Cx | AttributeId::Cy => {
+            &[ElementId::Circle, ElementId::Ellipse, ElementId::RadialGradient]
+        }
+        AttributeId::R => &[ElementId::Circle, ElementId::RadialGradient],
+        AttributeId::Fx | AttributeId::Fy => &[ElementId::RadialGradient],
+        AttributeId::Rx | AttributeId::Ry => &[ElementId::Ellipse, ElementId::Rect],
+        AttributeId::X1 | AttributeId::Y1 | AttributeId::X2 | AttributeId::Y2 => {
+            &[ElementId::Line, ElementId::LinearGradient]
+        }
+        AttributeId::D => &[ElementId::Path, ElementId::Glyph, ElementId::MissingGlyph],
+        AttributeId::Points => &[ElementId::Polygon, ElementId::Polyline],
+        AttributeId::Offset => &[ElementId::Stop],
+        AttributeId::GradientTransform | AttributeId::GradientUnits | AttributeId::SpreadMethod => {
+            &[ElementId::LinearGradient, ElementId::RadialGradient]
+        }
+          AttributeId::PatternTransform
+        | AttributeId::PatternUnits
+        | AttributeId::PatternContentUnits => &[ElementId::Pattern],
+        AttributeId::ClipPathUnits => &[ElementId::ClipPath],
+        AttributeId::MaskUnits | AttributeId::MaskContentUnits => &[ElementId::Mask],
+        AttributeId::FilterUnits | AttributeId::FilterRes => &[ElementId::Filter],
+          AttributeId::MarkerUnits
+        | AttributeId::MarkerWidth
+        | AttributeId::MarkerHeight
+        | AttributeId::RefX
+        | AttributeId::RefY
+        | AttributeId::Orient => &[ElementId::Marker],
+        AttributeId::ViewBox => {
+            &[ElementId::Marker, ElementId::Pattern, ElementId::Svg, ElementId::Symbol,
+              ElementId::View]
+        }
+        AttributeId::Transform => {
+            &[ElementId::A, ElementId::Circle, ElementId::ClipPath, ElementId::Defs,
+              ElementId::Ellipse, ElementId::ForeignObject, ElementId::G, ElementId::Image,
+              ElementId::Line, ElementId::Path, ElementId::Polygon, ElementId::Polyline,
+              ElementId::Rect, ElementId::Switch, ElementId::Text, ElementId::Use]
+        }
+        _ => return true,
+    };
+
+    list.contains(&id)
+}
+
+/// Checks that the attribute value is in the allowed range.
+fn is_valid_value(id: ElementId, aid: AttributeId, value: &AttributeValue) -> bool {
+    match aid {
+          AttributeId::R
+        | AttributeId::Rx
+        | AttributeId::Ry
+        | AttributeId::StrokeWidth
+        | AttributeId::FontSize
+        | AttributeId::MarkerWidth
+        | AttributeId::MarkerHeight => all_numbers(value, |n| n >= 0.0),
+        AttributeId::Width | AttributeId::Height => {
+            // 'width' and 'height' of the 'svg' and the 'use' elements can be negative,
+            // but not for the rendering.
+            all_numbers(value, |n| n >= 0.0)
+                || id == ElementId::Svg || id == ElementId::Use
+        }
+        AttributeId::StrokeDasharray | AttributeId::StdDeviation => {
+            all_numbers(value, |n| n >= 0.0)
+        }
+        AttributeId::StrokeMiterlimit => all_numbers(value, |n| n >= 1.0),
+          AttributeId::Opacity
+        | AttributeId::FillOpacity
+        | AttributeId::StrokeOpacity
+        | AttributeId::StopOpacity
+        | AttributeId::FloodOpacity
+        | AttributeId::Offset => all_numbers(value, |n| n >= 0.0 && n <= 1.0),
+        _ => true,
+    }
+}
+
+/// Checks all numbers in the value using the predicate.
+///
+/// Percentages are converted to fractions. Non-numeric values are ignored.
+fn all_numbers<P>(value: &AttributeValue, p: P) -> bool
+    where P: Fn(f64) -> bool
+{
+    let length = |num: f64, unit: LengthUnit| {
+        if unit == LengthUnit::Percent { num / 100.0 } else { num }
+    };
+
+    match *value {
+        AttributeValue::Number(n) => p(n),
+        AttributeValue::NumberList(ref list) => list.iter().all(|n| p(*n)),
+        AttributeValue::Length(l) => p(length(l.num, l.unit)),
+        AttributeValue::LengthList(ref list) => list.iter().all(|l| p(length(l.num, l.unit))),
+        _ => true,
+    }
+}
@@ -45,6 +45,19 @@ error_chain! {
             display("element crosslink")
         }
 
+        /// Elements reference each other through a chain of links and children.
+        ///
+        /// # Examples
+        ///
+        /// ```text
+        /// <g id="g1">
+        ///     <use xlink:href="#g1"/>
+        /// </g>
+        /// ```
+        ReferenceCycle(path: String) {
+            display("reference cycle: {}", path)
+        }
+
         /// Parsed document must have an `svg` element.
         NoSvgElement {
             display("the document does not have an SVG element")
@@ -0,0 +1,289 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+define_ids!(
+    /// List of all supported SVG attributes.
+    AttributeId, ATTRIBUTE_NAMES;
+    AccentHeight               => "accent-height",
+    Accumulate                 => "accumulate",
+    Additive                   => "additive",
+    AlignmentBaseline          => "alignment-baseline",
+    Alphabetic                 => "alphabetic",
+    Amplitude                  => "amplitude",
+    ArabicForm                 => "arabic-form",
+    Ascent                     => "ascent",
+    AttributeName              => "attributeName",
+    AttributeType              => "attributeType",
+    Azimuth                    => "azimuth",
+    BaseFrequency              => "baseFrequency",
+    BaseProfile                => "baseProfile",
+    BaselineShift              => "baseline-shift",
+    Bbox                       => "bbox",
+    Begin                      => "begin",
+    Bias                       => "bias",
+    By                         => "by",
+    CalcMode                   => "calcMode",
+    CapHeight                  => "cap-height",
+    Class                      => "class",
+    Clip                       => "clip",
+    ClipPath                   => "clip-path",
+    ClipRule                   => "clip-rule",
+    ClipPathUnits              => "clipPathUnits",
+    Color                      => "color",
+    ColorInterpolation         => "color-interpolation",
+    ColorInterpolationFilters  => "color-interpolation-filters",
+    ColorProfile               => "color-profile",
+    ColorRendering             => "color-rendering",
+    ContentScriptType          => "contentScriptType",
+    ContentStyleType           => "contentStyleType",
+    Cursor                     => "cursor",
+    Cx                         => "cx",
+    Cy                         => "cy",
+    D                          => "d",
+    Descent                    => "descent",
+    DiffuseConstant            => "diffuseConstant",
+    Direction                  => "direction",
+    Display                    => "display",
+    Divisor                    => "divisor",
+    DominantBaseline           => "dominant-baseline",
+    Dur                        => "dur",
+    Dx                         => "dx",
+    Dy                         => "dy",
+    EdgeMode                   => "edgeMode",
+    Elevation                  => "elevation",
+    EnableBackground           => "enable-background",
+    End                        => "end",
+    Exponent                   => "exponent",
+    ExternalResourcesRequired  => "externalResourcesRequired",
+    Fill                       => "fill",
+    FillOpacity                => "fill-opacity",
+    FillRule                   => "fill-rule",
+    Filter                     => "filter",
+    FilterRes                  => "filterRes",
+    FilterUnits                => "filterUnits",
+    FloodColor                 => "flood-color",
+    FloodOpacity               => "flood-opacity",
+    Font                       => "font",
+    FontFamily                 => "font-family",
+    FontSize                   => "font-size",
+    FontSizeAdjust             => "font-size-adjust",
+    FontStretch                => "font-stretch",
+    FontStyle                  => "font-style",
+    FontVariant                => "font-variant",
+    FontWeight                 => "font-weight",
+    Format                     => "format",
+    From                       => "from",
+    Fx                         => "fx",
+    Fy                         => "fy",
+    G1                         => "g1",
+    G2                         => "g2",
+    GlyphName                  => "glyph-name",
+    GlyphOrientationHorizontal => "glyph-orientation-horizontal",
+    GlyphOrientationVertical   => "glyph-orientation-vertical",
+    GlyphRef                   => "glyphRef",
+    GradientTransform          => "gradientTransform",
+    GradientUnits              => "gradientUnits",
+    Hanging                    => "hanging",
+    HatchContentUnits          => "hatchContentUnits",
+    HatchUnits                 => "hatchUnits",
+    Height                     => "height",
+    HorizAdvX                  => "horiz-adv-x",
+    HorizOriginX               => "horiz-origin-x",
+    HorizOriginY               => "horiz-origin-y",
+    Href                       => "href",
+    Id                         => "id",
+    Ideographic                => "ideographic",
+    ImageRendering             => "image-rendering",
+    In                         => "in",
+    In2                        => "in2",
+    Intercept                  => "intercept",
+    Isolation                  => "isolation",
+    K                          => "k",
+    K1                         => "k1",
+    K2                         => "k2",
+    K3                         => "k3",
+    K4                         => "k4",
+    KernelMatrix               => "kernelMatrix",
+    KernelUnitLength           => "kernelUnitLength",
+    Kerning                    => "kerning",
+    KeyPoints                  => "keyPoints",
+    KeySplines                 => "keySplines",
+    KeyTimes                   => "keyTimes",
+    Lang                       => "lang",
+    LengthAdjust               => "lengthAdjust",
+    LetterSpacing              => "letter-spacing",
+    LightingColor              => "lighting-color",
+    LimitingConeAngle          => "limitingConeAngle",
+    LineHeight                 => "line-height",
+    Local                      => "local",
+    Marker                     => "marker",
+    MarkerEnd                  => "marker-end",
+    MarkerMid                  => "marker-mid",
+    MarkerStart                => "marker-start",
+    MarkerHeight               => "markerHeight",
+    MarkerUnits                => "markerUnits",
+    MarkerWidth                => "markerWidth",
+    Mask                       => "mask",
+    MaskContentUnits           => "maskContentUnits",
+    MaskUnits                  => "maskUnits",
+    Mathematical               => "mathematical",
+    Max                        => "max",
+    Media                      => "media",
+    Method                     => "method",
+    Min                        => "min",
+    MixBlendMode               => "mix-blend-mode",
+    Mode                       => "mode",
+    Name                       => "name",
+    NumOctaves                 => "numOctaves",
+    Offset                     => "offset",
+    Onabort                    => "onabort",
+    Onactivate                 => "onactivate",
+    Onbegin                    => "onbegin",
+    Onclick                    => "onclick",
+    Onend                      => "onend",
+    Onerror                    => "onerror",
+    Onfocusin                  => "onfocusin",
+    Onfocusout                 => "onfocusout",
+    Onload                     => "onload",
+    Onmousedown                => "onmousedown",
+    Onmousemove                => "onmousemove",
+    Onmouseout                 => "onmouseout",
+    Onmouseover                => "onmouseover",
+    Onmouseup                  => "onmouseup",
+    Onrepeat                   => "onrepeat",
+    Onresize                   => "onresize",
+    Onscroll                   => "onscroll",
+    Onunload                   => "onunload",
+    Onzoom                     => "onzoom",
+    Opacity                    => "opacity",
+    Operator                   => "operator",
+    Order                      => "order",
+    Orient                     => "orient",
+    Orientation                => "orientation",
+    Origin                     => "origin",
+    Overflow                   => "overflow",
+    OverlinePosition           => "overline-position",
+    OverlineThickness          => "overline-thickness",
+    PaintOrder                 => "paint-order",
+    Panose1                    => "panose-1",
+    Path                       => "path",
+    PathLength                 => "pathLength",
+    PatternContentUnits        => "patternContentUnits",
+    PatternTransform           => "patternTransform",
+    PatternUnits               => "patternUnits",
+    Pitch                      => "pitch",
+    PointerEvents              => "pointer-events",
+    Points                     => "points",
+    PointsAtX                  => "pointsAtX",
+    PointsAtY                  => "pointsAtY",
+    PointsAtZ                  => "pointsAtZ",
+    PreserveAlpha              => "preserveAlpha",
+    PreserveAspectRatio        => "preserveAspectRatio",
+    PrimitiveUnits             => "primitiveUnits",
+    R                          => "r",
+    Radius                     => "radius",
+    RefX                       => "refX",
+    RefY                       => "refY",
+    RenderingIntent            => "rendering-intent",
+    RepeatCount                => "repeatCount",
+    RepeatDur                  => "repeatDur",
+    RequiredExtensions         => "requiredExtensions",
+    RequiredFeatures           => "requiredFeatures",
+    Restart                    => "restart",
+    Result                     => "result",
+    Rotate                     => "rotate",
+    Rx                         => "rx",
+    Ry                         => "ry",
+    Scale                      => "scale",
+    Seed                       => "seed",
+    ShapeRendering             => "shape-rendering",
+    Slope                      => "slope",
+    SolidColor                 => "solid-color",
+    SolidOpacity               => "solid-opacity",
+    Spacing                    => "spacing",
+    SpecularConstant           => "specularConstant",
+    SpecularExponent           => "specularExponent",
+    SpreadMethod               => "spreadMethod",
+    StartOffset                => "startOffset",
+    StdDeviation               => "stdDeviation",
+    Stemh                      => "stemh",
+    Stemv                      => "stemv",
+    StitchTiles                => "stitchTiles",
+    StopColor                  => "stop-color",
+    StopOpacity                => "stop-opacity",
+    StrikethroughPosition      => "strikethrough-position",
+    StrikethroughThickness     => "strikethrough-thickness",
+    String                     => "string",
+    Stroke                     => "stroke",
+    StrokeDasharray            => "stroke-dasharray",
+    StrokeDashoffset           => "stroke-dashoffset",
+    StrokeLinecap              => "stroke-linecap",
+    StrokeLinejoin             => "stroke-linejoin",
+    StrokeMiterlimit           => "stroke-miterlimit",
+    StrokeOpacity              => "stroke-opacity",
+    StrokeWidth                => "stroke-width",
+    Style                      => "style",
+    SurfaceScale               => "surfaceScale",
+    SystemLanguage             => "systemLanguage",
+    TableValues                => "tableValues",
+    Target                     => "target",
+    TargetX                    => "targetX",
+    TargetY                    => "targetY",
+    TextAnchor                 => "text-anchor",
+    TextDecoration             => "text-decoration",
+    TextRendering              => "text-rendering",
+    TextLength                 => "textLength",
+    Title                      => "title",
+    To                         => "to",
+    Transform                  => "transform",
+    Type                       => "type",
+    U1                         => "u1",
+    U2                         => "u2",
+    UnderlinePosition          => "underline-position",
+    UnderlineThickness         => "underline-thickness",
+    Unicode                    => "unicode",
+    UnicodeBidi                => "unicode-bidi",
+    UnicodeRange               => "unicode-range",
+    UnitsPerEm                 => "units-per-em",
+    VAlphabetic                => "v-alphabetic",
+    VHanging                   => "v-hanging",
+    VIdeographic               => "v-ideographic",
+    VMathematical              => "v-mathematical",
+    Values                     => "values",
+    VectorEffect               => "vector-effect",
+    Version                    => "version",
+    VertAdvY                   => "vert-adv-y",
+    VertOriginX                => "vert-origin-x",
+    VertOriginY                => "vert-origin-y",
+    ViewBox                    => "viewBox",
+    ViewTarget                 => "viewTarget",
+    Visibility                 => "visibility",
+    Width                      => "width",
+    Widths                     => "widths",
+    WordSpacing                => "word-spacing",
+    WritingMode                => "writing-mode",
+    X                          => "x",
+    XHeight                    => "x-height",
+    X1                         => "x1",
+    X2                         => "x2",
+    XChannelSelector           => "xChannelSelector",
+    XlinkActuate               => "xlink:actuate",
+    XlinkArcrole               => "xlink:arcrole",
+    XlinkHref                  => "xlink:href",
+    XlinkRole                  => "xlink:role",
+    XlinkShow                  => "xlink:show",
+    XlinkTitle                 => "xlink:title",
+    XlinkType                  => "xlink:type",
+    XmlBase                    => "xml:base",
+    XmlLang                    => "xml:lang",
+    XmlSpace                   => "xml:space",
+    Xmlns                      => "xmlns",
+    XmlnsXlink                 => "xmlns:xlink",
+    Y                          => "y",
+    Y1                         => "y1",
+    Y2                         => "y2",
+    YChannelSelector           => "yChannelSelector",
+    Z                          => "z",
+    ZoomAndPan                 => "zoomAndPan",
+);
@@ -0,0 +1,96 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+define_ids!(
+    /// List of all supported SVG elements.
+    ElementId, ELEMENT_NAMES;
+    A                   => "a",
+    AltGlyph            => "altGlyph",
+    AltGlyphDef         => "altGlyphDef",
+    AltGlyphItem        => "altGlyphItem",
+    Animate             => "animate",
+    AnimateColor        => "animateColor",
+    AnimateMotion       => "animateMotion",
+    AnimateTransform    => "animateTransform",
+    Circle              => "circle",
+    ClipPath            => "clipPath",
+    ColorProfile        => "color-profile",
+    Cursor              => "cursor",
+    Defs                => "defs",
+    Desc                => "desc",
+    Ellipse             => "ellipse",
+    FeBlend             => "feBlend",
+    FeColorMatrix       => "feColorMatrix",
+    FeComponentTransfer => "feComponentTransfer",
+    FeComposite         => "feComposite",
+    FeConvolveMatrix    => "feConvolveMatrix",
+    FeDiffuseLighting   => "feDiffuseLighting",
+    FeDisplacementMap   => "feDisplacementMap",
+    FeDistantLight      => "feDistantLight",
+    FeDropShadow        => "feDropShadow",
+    FeFlood             => "feFlood",
+    FeFuncA             => "feFuncA",
+    FeFuncB             => "feFuncB",
+    FeFuncG             => "feFuncG",
+    FeFuncR             => "feFuncR",
+    FeGaussianBlur      => "feGaussianBlur",
+    FeImage             => "feImage",
+    FeMerge             => "feMerge",
+    FeMergeNode         => "feMergeNode",
+    FeMorphology        => "feMorphology",
+    FeOffset            => "feOffset",
+    FePointLight        => "fePointLight",
+    FeSpecularLighting  => "feSpecularLighting",
+    FeSpotLight         => "feSpotLight",
+    FeTile              => "feTile",
+    FeTurbulence        => "feTurbulence",
+    Filter              => "filter",
+    Font                => "font",
+    FontFace            => "font-face",
+    FontFaceFormat      => "font-face-format",
+    FontFaceName        => "font-face-name",
+    FontFaceSrc         => "font-face-src",
+    FontFaceUri         => "font-face-uri",
+    ForeignObject       => "foreignObject",
+    G                   => "g",
+    Glyph               => "glyph",
+    GlyphRef            => "glyphRef",
+    Hatch               => "hatch",
+    HatchPath           => "hatchpath",
+    Hkern               => "hkern",
+    Image               => "image",
+    Line                => "line",
+    LinearGradient      => "linearGradient",
+    Marker              => "marker",
+    Mask                => "mask",
+    Mesh                => "mesh",
+    MeshGradient        => "meshgradient",
+    MeshPatch           => "meshpatch",
+    MeshRow             => "meshrow",
+    Metadata            => "metadata",
+    MissingGlyph        => "missing-glyph",
+    Mpath               => "mpath",
+    Path                => "path",
+    Pattern             => "pattern",
+    Polygon             => "polygon",
+    Polyline            => "polyline",
+    RadialGradient      => "radialGradient",
+    Rect                => "rect",
+    Script              => "script",
+    Set                 => "set",
+    SolidColor          => "solidcolor",
+    Stop                => "stop",
+    Style               => "style",
+    Svg                 => "svg",
+    Switch              => "switch",
+    Symbol              => "symbol",
+    Text                => "text",
+    TextPath            => "textPath",
+    Title               => "title",
+    Tref                => "tref",
+    Tspan               => "tspan",
+    Use                 => "use",
+    View                => "view",
+    Vkern               => "vkern",
+);
@@ -0,0 +1,124 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! This module contains SVG names.
+//!
+//! `svgparser` supports only SVG 1.1 names, so we have our own lists,
+//! which also include SVG 2 names.
+
+use svgparser;
+
+// Names must be sorted, because `from_name` is using a binary search.
+macro_rules! define_ids {
+    ($(#[$meta:meta])* $name:ident, $list:ident; $($id:ident => $text:expr),+ $(,)*) => (
+        $(#[$meta])*
+        #[derive(Copy,Clone,Eq,PartialEq,Ord,PartialOrd,Hash)]
+        #[allow(missing_docs)]
+        pub enum $name {
+            $($id),+
+        }
+
+        pub static $list: &'static [(&'static str, $name)] = &[
+            $(($text, $name::$id)),+
+        ];
+
+        impl $name {
+            /// Converts name into id.
+            pub fn from_name(text: &str) -> Option<$name> {
+                match $list.binary_search_by(|&(name, _)| name.cmp(text)) {
+                    Ok(idx) => Some($list[idx].1),
+                    Err(_) => None,
+                }
+            }
+
+            /// Converts id into name.
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    $($name::$id => $text),+
+                }
+            }
+        }
+
+        impl ::std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, "{}", self.name())
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, "{}", self.name())
+            }
+        }
+    )
+}
+
+mod attribute_id;
+mod element_id;
+mod value_id;
+
+pub use self::attribute_id::AttributeId;
+pub use self::element_id::ElementId;
+pub use self::value_id::ValueId;
+
+// All SVG 1.1 names are present in our lists, so conversions from `svgparser` can't fail.
+
+/// Converts an `svgparser` element id into ours.
+pub fn from_parser_element(id: svgparser::ElementId) -> ElementId {
+    ElementId::from_name(id.name()).unwrap()
+}
+
+/// Converts an `svgparser` attribute id into ours.
+pub fn from_parser_attribute(id: svgparser::AttributeId) -> AttributeId {
+    AttributeId::from_name(id.name()).unwrap()
+}
+
+/// Converts an `svgparser` value id into ours.
+pub fn from_parser_value(id: svgparser::ValueId) -> ValueId {
+    ValueId::from_name(id.name()).unwrap()
+}
+
+/// Converts an element id into the `svgparser` one.
+///
+/// Returns `None` for SVG 2 elements.
+pub fn to_parser_element(id: ElementId) -> Option<svgparser::ElementId> {
+    svgparser::ElementId::from_name(id.name())
+}
+
+/// Converts an attribute id into the `svgparser` one.
+///
+/// Returns `None` for SVG 2 attributes.
+pub fn to_parser_attribute(id: AttributeId) -> Option<svgparser::AttributeId> {
+    svgparser::AttributeId::from_name(id.name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_sorted {
+        ($name:ident, $module:ident, $list:ident) => (
+            #[test]
+            fn $name() {
+                for pair in super::$module::$list.windows(2) {
+                    assert!(pair[0].0 < pair[1].0, "{} >= {}", pair[0].0, pair[1].0);
+                }
+            }
+        )
+    }
+
+    test_sorted!(elements_sorted, element_id, ELEMENT_NAMES);
+    test_sorted!(attributes_sorted, attribute_id, ATTRIBUTE_NAMES);
+    test_sorted!(values_sorted, value_id, VALUE_NAMES);
+
+    #[test]
+    fn from_name_1() {
+        assert_eq!(ElementId::from_name("a"), Some(ElementId::A));
+        assert_eq!(ElementId::from_name("mesh"), Some(ElementId::Mesh));
+        assert_eq!(ElementId::from_name("zzz"), None);
+        assert_eq!(AttributeId::from_name("paint-order"), Some(AttributeId::PaintOrder));
+        assert_eq!(AttributeId::from_name("xlink:href"), Some(AttributeId::XlinkHref));
+        assert_eq!(ValueId::from_name("non-scaling-stroke"), Some(ValueId::NonScalingStroke));
+    }
+}
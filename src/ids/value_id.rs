@@ -0,0 +1,163 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+define_ids!(
+    /// List of all supported SVG predefined values.
+    ValueId, VALUE_NAMES;
+    N100               => "100",
+    N200               => "200",
+    N300               => "300",
+    N400               => "400",
+    N500               => "500",
+    N600               => "600",
+    N700               => "700",
+    N800               => "800",
+    N900               => "900",
+    Accumulate         => "accumulate",
+    AfterEdge          => "after-edge",
+    All                => "all",
+    Alphabetic         => "alphabetic",
+    Auto               => "auto",
+    AutoStartReverse   => "auto-start-reverse",
+    Baseline           => "baseline",
+    BeforeEdge         => "before-edge",
+    Bevel              => "bevel",
+    BidiOverride       => "bidi-override",
+    Blink              => "blink",
+    Block              => "block",
+    Bold               => "bold",
+    Bolder             => "bolder",
+    Butt               => "butt",
+    Central            => "central",
+    Collapse           => "collapse",
+    Color              => "color",
+    ColorBurn          => "color-burn",
+    ColorDodge         => "color-dodge",
+    Compact            => "compact",
+    Condensed          => "condensed",
+    CrispEdges         => "crispEdges",
+    Crosshair          => "crosshair",
+    CurrentColor       => "currentColor",
+    Darken             => "darken",
+    Default            => "default",
+    Difference         => "difference",
+    EResize            => "e-resize",
+    Embed              => "embed",
+    End                => "end",
+    Evenodd            => "evenodd",
+    Exclusion          => "exclusion",
+    Expanded           => "expanded",
+    ExtraCondensed     => "extra-condensed",
+    ExtraExpanded      => "extra-expanded",
+    Fill               => "fill",
+    FixedPosition      => "fixed-position",
+    GeometricPrecision => "geometricPrecision",
+    Hanging            => "hanging",
+    HardLight          => "hard-light",
+    Help               => "help",
+    Hidden             => "hidden",
+    Hue                => "hue",
+    Ideographic        => "ideographic",
+    Inherit            => "inherit",
+    Inline             => "inline",
+    InlineTable        => "inline-table",
+    Isolate            => "isolate",
+    Italic             => "italic",
+    Large              => "large",
+    Larger             => "larger",
+    Lighten            => "lighten",
+    Lighter            => "lighter",
+    LineThrough        => "line-through",
+    LinearRGB          => "linearRGB",
+    ListItem           => "list-item",
+    Lr                 => "lr",
+    LrTb               => "lr-tb",
+    Ltr                => "ltr",
+    Luminosity         => "luminosity",
+    Marker             => "marker",
+    Markers            => "markers",
+    Mathematical       => "mathematical",
+    Medium             => "medium",
+    Middle             => "middle",
+    Miter              => "miter",
+    Move               => "move",
+    Multiply           => "multiply",
+    NResize            => "n-resize",
+    Narrower           => "narrower",
+    NeResize           => "ne-resize",
+    NoChange           => "no-change",
+    NonRotation        => "non-rotation",
+    NonScalingSize     => "non-scaling-size",
+    NonScalingStroke   => "non-scaling-stroke",
+    None               => "none",
+    Nonzero            => "nonzero",
+    Normal             => "normal",
+    NwResize           => "nw-resize",
+    ObjectBoundingBox  => "objectBoundingBox",
+    Oblique            => "oblique",
+    OptimizeLegibility => "optimizeLegibility",
+    OptimizeQuality    => "optimizeQuality",
+    OptimizeSpeed      => "optimizeSpeed",
+    Overlay            => "overlay",
+    Overline           => "overline",
+    Pad                => "pad",
+    Painted            => "painted",
+    Pointer            => "pointer",
+    Reflect            => "reflect",
+    Repeat             => "repeat",
+    ResetSize          => "reset-size",
+    Rl                 => "rl",
+    RlTb               => "rl-tb",
+    Round              => "round",
+    Rtl                => "rtl",
+    RunIn              => "run-in",
+    SResize            => "s-resize",
+    SRGB               => "sRGB",
+    Saturation         => "saturation",
+    Screen             => "screen",
+    Scroll             => "scroll",
+    SeResize           => "se-resize",
+    SemiCondensed      => "semi-condensed",
+    SemiExpanded       => "semi-expanded",
+    Small              => "small",
+    SmallCaps          => "small-caps",
+    Smaller            => "smaller",
+    SoftLight          => "soft-light",
+    Square             => "square",
+    Start              => "start",
+    Stroke             => "stroke",
+    Sub                => "sub",
+    Super              => "super",
+    SwResize           => "sw-resize",
+    Table              => "table",
+    TableCaption       => "table-caption",
+    TableCell          => "table-cell",
+    TableColumn        => "table-column",
+    TableColumnGroup   => "table-column-group",
+    TableFooterGroup   => "table-footer-group",
+    TableHeaderGroup   => "table-header-group",
+    TableRow           => "table-row",
+    TableRowGroup      => "table-row-group",
+    Tb                 => "tb",
+    TbRl               => "tb-rl",
+    Text               => "text",
+    TextAfterEdge      => "text-after-edge",
+    TextBeforeEdge     => "text-before-edge",
+    UltraCondensed     => "ultra-condensed",
+    UltraExpanded      => "ultra-expanded",
+    Underline          => "underline",
+    UseScript          => "use-script",
+    UserSpaceOnUse     => "userSpaceOnUse",
+    Visible            => "visible",
+    VisibleFill        => "visibleFill",
+    VisiblePainted     => "visiblePainted",
+    VisibleStroke      => "visibleStroke",
+    WResize            => "w-resize",
+    Wait               => "wait",
+    Wider              => "wider",
+    XLarge             => "x-large",
+    XSmall             => "x-small",
+    XxLarge            => "xx-large",
+    XxSmall            => "xx-small",
+);
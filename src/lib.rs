@@ -77,15 +77,34 @@ macro_rules! assert_eq_text {
     })
 }
 
+#[cfg(test)]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr, $eps:expr) => ({
+        use test_utils::ApproxEq;
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                if !left_val.approx_eq(right_val, $eps) {
+                    panic!("assertion failed: `(left ~= right)` \
+                           \nleft:  `{:?}`\nright: `{:?}`",
+                           left_val, right_val)
+                }
+            }
+        }
+    })
+}
+
 #[macro_use]
 mod traits;
 mod attribute;
 mod dom;
 mod error;
+mod ids;
 mod name;
 mod parser;
 mod types;
 mod writer;
+#[cfg(test)]
+mod test_utils;
 
 
 pub use attribute::*;
@@ -103,10 +122,13 @@ pub use parser::{
     ParseOptions,
 };
 
-pub use svgparser::{
+pub use ids::{
     AttributeId,
-    ChainedErrorExt,
     ElementId,
-    ErrorPos,
     ValueId,
 };
+
+pub use svgparser::{
+    ChainedErrorExt,
+    ErrorPos,
+};
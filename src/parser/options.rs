@@ -50,6 +50,13 @@ pub struct ParseOptions {
     ///
     /// Otherwise `UnsupportedPaintFallback` error will occur during parsing.
     pub skip_paint_fallback: bool,
+
+    /// Parse `transform`, `gradientTransform` and `patternTransform` attributes
+    /// as `TransformList` and not as `Transform`.
+    ///
+    /// `TransformList` retains all operations, so the attribute will be written as is,
+    /// while `Transform` is always written as a single matrix.
+    pub parse_transform_lists: bool,
 }
 
 impl Default for ParseOptions {
@@ -64,6 +71,7 @@ impl Default for ParseOptions {
             skip_invalid_attributes: false,
             skip_invalid_css: false,
             skip_paint_fallback: false,
+            parse_transform_lists: false,
         }
     }
 }
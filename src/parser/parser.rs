@@ -6,6 +6,7 @@ use std::fmt::Write;
 use std::str;
 use std::collections::HashMap;
 
+use svgparser;
 use svgparser::{
     xmlparser,
     style,
@@ -18,6 +19,7 @@ use svgparser::{
 };
 
 use error::Result;
+use ids;
 use {
     AttributeId,
     AttributeValue,
@@ -32,10 +34,18 @@ use {
 };
 use types::{
     path,
+    Angle,
+    AngleList,
+    AspectRatio,
     Color,
+    IccColor,
     Length,
     LengthUnit,
+    PaintOrder,
+    Points,
+    Rect,
     Transform,
+    TransformList,
 };
 
 use super::{
@@ -130,7 +140,7 @@ pub fn parse_svg(text: &str, opt: &ParseOptions) -> Result<Document> {
         return Err(ErrorKind::EmptyDocument.into());
     }
 
-    match doc.children().nth(1).and_then(|n: Node| n.children().svg().nth(0)) {
+    match doc.children().svg().next() {
         Some((id, _)) => {
             if id != ElementId::Svg {
                 return Err(ErrorKind::NoSvgElement.into());
@@ -190,10 +200,14 @@ fn process_token<'a>(
         svg::Token::ElementStart(tag_name) => {
             let curr_node = match tag_name {
                 svg::Name::Xml(name) => {
-                    doc.create_element(name)
+                    // SVG 2 elements are unknown to svgparser.
+                    match ElementId::from_name(name) {
+                        Some(eid) => doc.create_element(eid),
+                        None => doc.create_element(name),
+                    }
                 }
                 svg::Name::Svg(eid) => {
-                    doc.create_element(eid)
+                    doc.create_element(ids::from_parser_element(eid))
                 }
             };
 
@@ -204,16 +218,25 @@ fn process_token<'a>(
             let curr_node = node.as_mut().unwrap();
             match name {
                 svg::Name::Xml(name) => {
-                    if opt.parse_unknown_attributes {
-                        if curr_node.is_svg_element() {
-                            parse_non_svg_attribute(curr_node, name, value, post_data);
-                        } else {
-                            curr_node.set_attribute((name, value.to_str()));
+                    // SVG 2 attributes are unknown to svgparser.
+                    match AttributeId::from_name(name) {
+                        Some(aid) if curr_node.is_svg_element() => {
+                            parse_svg_attribute(curr_node, aid, value, post_data, opt)?;
+                        }
+                        _ => {
+                            if opt.parse_unknown_attributes {
+                                if curr_node.is_svg_element() {
+                                    parse_non_svg_attribute(curr_node, name, value, post_data);
+                                } else {
+                                    curr_node.set_attribute((name, value.to_str()));
+                                }
+                            }
                         }
                     }
                 }
                 svg::Name::Svg(aid) => {
                     if curr_node.is_svg_element() {
+                        let aid = ids::from_parser_attribute(aid);
                         parse_svg_attribute(curr_node, aid, value, post_data, opt)?;
                     }
                 }
@@ -301,7 +324,7 @@ fn process_token<'a>(
     // which is faster
     if parent.node_type() == NodeType::Root {
         // check that the first element of the doc is 'svg'
-        if let Some((id, _)) = doc.children().nth(1).and_then(|n: Node| n.children().svg().nth(0)) {
+        if let Some((id, _)) = doc.children().svg().next() {
             if id != ElementId::Svg {
                 return Err(ErrorKind::NoSvgElement.into());
             }
@@ -333,9 +356,16 @@ fn parse_svg_attribute<'a>(
           AttributeId::Transform
         | AttributeId::GradientTransform
         | AttributeId::PatternTransform => {
-            let ts = Transform::from_span(value)?;
-            if !ts.is_default() {
-                node.set_attribute((id, AttributeValue::Transform(ts)));
+            if opt.parse_transform_lists {
+                let list = TransformList::from_span(value)?;
+                if !list.is_empty() {
+                    node.set_attribute((id, AttributeValue::TransformList(list)));
+                }
+            } else {
+                let ts = Transform::from_span(value)?;
+                if !ts.is_default() {
+                    node.set_attribute((id, AttributeValue::Transform(ts)));
+                }
             }
         }
         AttributeId::D => {
@@ -376,11 +406,49 @@ pub fn parse_svg_attribute_value<'a>(
     entitis: &Entities<'a>,
     opt: &ParseOptions,
 ) -> Result<()> {
+    let parser_id = match id {
+        // 'href' has the same syntax as 'xlink:href'.
+        AttributeId::Href => svgparser::AttributeId::XlinkHref,
+        // svgparser parses them as number lists and strings.
+          AttributeId::GlyphOrientationHorizontal
+        | AttributeId::GlyphOrientationVertical
+        | AttributeId::Orient
+        | AttributeId::Points
+        | AttributeId::PreserveAspectRatio
+        | AttributeId::Rotate
+        | AttributeId::ViewBox => {
+            return parse_custom_attribute_value(node, id, span, opt);
+        }
+        _ => {
+            match ids::to_parser_attribute(id) {
+                Some(aid) => aid,
+                None => return parse_custom_attribute_value(node, id, span, opt),
+            }
+        }
+    };
+
+    // SVG 2 elements are parsed just like the 'g' element,
+    // since svgparser checks the element only for a few text and animation attributes.
     let tag_id = node.tag_id().unwrap();
+    let parser_tag_id = ids::to_parser_element(tag_id).unwrap_or(svgparser::ElementId::G);
 
-    let av = match ParserAttributeValue::from_span(tag_id, id, span) {
+    let av = match ParserAttributeValue::from_span(parser_tag_id, parser_id, span) {
         Ok(av) => av,
         Err(e) => {
+            // svgparser supports only SVG 1.1 colors, so we have to check for CSS 3/4 ones,
+            // like 'rgba()' or 'transparent'.
+            if is_color_attribute(id) {
+                if let Ok(c) = Color::from_span(span) {
+                    node.set_attribute((id, c));
+                    return Ok(());
+                }
+
+                if let Ok(c) = IccColor::from_span(span) {
+                    node.set_attribute((id, c));
+                    return Ok(());
+                }
+            }
+
             return if opt.skip_invalid_attributes {
                 warn!("Attribute '{}' has an invalid value: '{}'.", id, span);
                 Ok(())
@@ -443,8 +511,11 @@ pub fn parse_svg_attribute_value<'a>(
         ParserAttributeValue::Color(v) => {
             Some(AttributeValue::Color(Color::new(v.red, v.green, v.blue)))
         }
+        ParserAttributeValue::PredefValue(svgparser::ValueId::CurrentColor) => {
+            Some(AttributeValue::CurrentColor)
+        }
         ParserAttributeValue::PredefValue(v) => {
-            Some(AttributeValue::PredefValue(v))
+            Some(AttributeValue::PredefValue(ids::from_parser_value(v)))
         }
         ParserAttributeValue::EntityRef(link) => {
             match entitis.get(link) {
@@ -475,6 +546,168 @@ pub fn parse_svg_attribute_value<'a>(
     Ok(())
 }
 
+fn is_color_attribute(id: AttributeId) -> bool {
+    match id {
+          AttributeId::Color
+        | AttributeId::Fill
+        | AttributeId::FloodColor
+        | AttributeId::LightingColor
+        | AttributeId::StopColor
+        | AttributeId::Stroke => true,
+        _ => false,
+    }
+}
+
+/// Parses attributes, which are not supported by svgparser or have a different representation.
+fn parse_custom_attribute_value(
+    node: &mut Node,
+    id: AttributeId,
+    span: StrSpan,
+    opt: &ParseOptions,
+) -> Result<()> {
+    fn parse_predef(text: &str, list: &[ValueId]) -> Option<AttributeValue> {
+        match ValueId::from_name(text) {
+            Some(v) if v == ValueId::Inherit || list.contains(&v) => {
+                Some(AttributeValue::PredefValue(v))
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_angle(span: StrSpan) -> Option<AttributeValue> {
+        Angle::from_span(span).ok().map(AttributeValue::Angle)
+    }
+
+    let text = span.to_str().trim();
+    let val = match id {
+        AttributeId::GlyphOrientationHorizontal => {
+            parse_predef(text, &[]).or_else(|| parse_angle(span))
+        }
+        AttributeId::GlyphOrientationVertical => {
+            parse_predef(text, &[ValueId::Auto]).or_else(|| parse_angle(span))
+        }
+        AttributeId::HatchContentUnits | AttributeId::HatchUnits => {
+            parse_predef(text, &[ValueId::UserSpaceOnUse, ValueId::ObjectBoundingBox])
+        }
+        AttributeId::Isolation => {
+            parse_predef(text, &[ValueId::Auto, ValueId::Isolate])
+        }
+        AttributeId::MixBlendMode => {
+            parse_predef(text, &[
+                ValueId::Normal,
+                ValueId::Multiply,
+                ValueId::Screen,
+                ValueId::Overlay,
+                ValueId::Darken,
+                ValueId::Lighten,
+                ValueId::ColorDodge,
+                ValueId::ColorBurn,
+                ValueId::HardLight,
+                ValueId::SoftLight,
+                ValueId::Difference,
+                ValueId::Exclusion,
+                ValueId::Hue,
+                ValueId::Saturation,
+                ValueId::Color,
+                ValueId::Luminosity,
+            ])
+        }
+        AttributeId::PaintOrder => {
+            parse_predef(text, &[]).or_else(|| {
+                PaintOrder::from_span(span).ok().map(AttributeValue::PaintOrder)
+            })
+        }
+        AttributeId::Orient => {
+            parse_predef(text, &[ValueId::Auto, ValueId::AutoStartReverse])
+                .or_else(|| parse_angle(span))
+        }
+        AttributeId::Points => {
+            match Points::from_span(span) {
+                // Ignore an empty list, like any other list.
+                Ok(ref points) if points.is_empty() => return Ok(()),
+                Ok(points) => Some(AttributeValue::Points(points)),
+                Err(_) => None,
+            }
+        }
+        AttributeId::PreserveAspectRatio => {
+            AspectRatio::from_span(span).ok().map(AttributeValue::AspectRatio)
+        }
+        AttributeId::ViewBox => {
+            Rect::from_span(span).ok().map(AttributeValue::ViewBox)
+        }
+        AttributeId::Pitch => {
+            Length::from_span(span).ok().map(|l| {
+                AttributeValue::Length(Length::new(l.num, prepare_length_unit(l.unit, opt)))
+            })
+        }
+        AttributeId::Rotate => {
+            match node.tag_id().unwrap() {
+                  ElementId::AltGlyph
+                | ElementId::Text
+                | ElementId::Tref
+                | ElementId::Tspan => {
+                    let list: Option<AngleList> = text
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.parse().ok())
+                        .collect();
+
+                    match list {
+                        // Ignore an empty list, like any other list.
+                        Some(ref list) if list.is_empty() => return Ok(()),
+                        Some(list) => Some(AttributeValue::AngleList(list)),
+                        None => None,
+                    }
+                }
+                ElementId::Hatch => parse_angle(span),
+                // 'animateMotion' has its own 'rotate'.
+                _ => Some(AttributeValue::String(text.to_string())),
+            }
+        }
+        AttributeId::SolidColor => {
+            if text == "currentColor" {
+                Some(AttributeValue::CurrentColor)
+            } else {
+                parse_predef(text, &[]).or_else(|| {
+                    Color::from_span(span).ok().map(AttributeValue::Color)
+                }).or_else(|| {
+                    IccColor::from_span(span).ok().map(AttributeValue::IccColor)
+                })
+            }
+        }
+        AttributeId::SolidOpacity => {
+            parse_predef(text, &[]).or_else(|| {
+                text.parse::<f64>().ok().map(|n| AttributeValue::Number(n.max(0.0).min(1.0)))
+            })
+        }
+        AttributeId::VectorEffect => {
+            parse_predef(text, &[
+                ValueId::None,
+                ValueId::NonScalingStroke,
+                ValueId::NonScalingSize,
+                ValueId::NonRotation,
+                ValueId::FixedPosition,
+            ])
+        }
+        _ => Some(AttributeValue::String(text.to_string())),
+    };
+
+    match val {
+        Some(v) => node.set_attribute((id, v)),
+        None => {
+            if opt.skip_invalid_attributes {
+                warn!("Attribute '{}' has an invalid value: '{}'.", id, span);
+            } else {
+                let pos = Stream::from_span(span).gen_error_pos();
+                let e = svgparser::Error::from(svgparser::ErrorKind::InvalidAttributeValue(pos));
+                return Err(e.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn parse_non_svg_attribute<'a>(
     node: &mut Node,
     name: &str,
@@ -522,11 +755,21 @@ fn parse_style_attribute<'a>(
     for token in style::Tokenizer::from_span(span) {
         match token? {
             style::Token::XmlAttribute(name, value) => {
-                if opt.parse_unknown_attributes {
-                    node.set_attribute((name, value));
+                // SVG 2 attributes are unknown to svgparser.
+                match AttributeId::from_name(name) {
+                    Some(id) => {
+                        let value = StrSpan::from_str(value);
+                        parse_svg_attribute_value(node, id, value, links, entitis, opt)?;
+                    }
+                    None => {
+                        if opt.parse_unknown_attributes {
+                            node.set_attribute((name, value));
+                        }
+                    }
                 }
             }
             style::Token::SvgAttribute(id, value) => {
+                let id = ids::from_parser_attribute(id);
                 parse_svg_attribute_value(node, id, value, links, entitis, opt)?;
             }
             style::Token::EntityRef(name) => {
@@ -576,8 +819,11 @@ fn resolve_fallback(d: &mut LinkData) -> Result<()> {
     match d.fallback {
         Some(fallback) => {
             match fallback {
+                PaintFallback::PredefValue(svgparser::ValueId::CurrentColor) => {
+                    d.node.set_attribute((d.attr_id, AttributeValue::CurrentColor));
+                }
                 PaintFallback::PredefValue(v) => {
-                    d.node.set_attribute((d.attr_id, v));
+                    d.node.set_attribute((d.attr_id, ids::from_parser_value(v)));
                 }
                 PaintFallback::Color(c) => {
                     d.node.set_attribute((d.attr_id, Color::new(c.red, c.green, c.blue)));
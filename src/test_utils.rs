@@ -0,0 +1,39 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use {
+    Rect,
+    Transform,
+};
+
+// Compares calculated values with a custom precision.
+//
+// `FuzzyEq` can't be used for them, because trigonometric functions
+// and numerical algorithms are not exact, especially for values near zero.
+pub trait ApproxEq {
+    fn approx_eq(&self, other: &Self, eps: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &f64, eps: f64) -> bool {
+        (self - other).abs() < eps
+    }
+}
+
+impl ApproxEq for Rect {
+    fn approx_eq(&self, other: &Rect, eps: f64) -> bool {
+           self.x.approx_eq(&other.x, eps)
+        && self.y.approx_eq(&other.y, eps)
+        && self.width.approx_eq(&other.width, eps)
+        && self.height.approx_eq(&other.height, eps)
+    }
+}
+
+impl ApproxEq for Transform {
+    fn approx_eq(&self, other: &Transform, eps: f64) -> bool {
+           self.a.approx_eq(&other.a, eps) && self.b.approx_eq(&other.b, eps)
+        && self.c.approx_eq(&other.c, eps) && self.d.approx_eq(&other.d, eps)
+        && self.e.approx_eq(&other.e, eps) && self.f.approx_eq(&other.f, eps)
+    }
+}
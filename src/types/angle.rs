@@ -0,0 +1,216 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::f64;
+use std::fmt;
+use std::str::FromStr;
+
+use svgparser::{
+    Error as ParseError,
+    ErrorKind as ParseErrorKind,
+    Stream,
+    StrSpan,
+    StreamExt,
+};
+
+use {
+    ParseFromSpan,
+    WriteBuffer,
+    WriteOptions,
+    ToStringWithOptions,
+};
+use super::number::FuzzyEq;
+
+/// List of all SVG angle units.
+#[derive(Clone,Copy,PartialEq,Debug)]
+#[allow(missing_docs)]
+pub enum AngleUnit {
+    /// A number without a unit, which is treated as degrees.
+    None,
+    Degrees,
+    Radians,
+    Gradians,
+    Turns,
+}
+
+impl AngleUnit {
+    // Number of units in a full turn.
+    fn turn(&self) -> f64 {
+        match *self {
+              AngleUnit::None
+            | AngleUnit::Degrees => 360.0,
+            AngleUnit::Radians => 2.0 * f64::consts::PI,
+            AngleUnit::Gradians => 400.0,
+            AngleUnit::Turns => 1.0,
+        }
+    }
+}
+
+/// Representation of the [`<angle>`] type.
+///
+/// [`<angle>`]: https://www.w3.org/TR/SVG/types.html#DataTypeAngle
+#[derive(Clone,Copy,PartialEq,Debug)]
+#[allow(missing_docs)]
+pub struct Angle {
+    pub num: f64,
+    pub unit: AngleUnit,
+}
+
+impl Angle {
+    /// Constructs a new angle.
+    #[inline]
+    pub fn new(num: f64, unit: AngleUnit) -> Angle {
+        Angle {
+            num: num,
+            unit: unit,
+        }
+    }
+
+    /// Constructs a new angle with `AngleUnit::None`.
+    #[inline]
+    pub fn new_number(num: f64) -> Angle {
+        Angle {
+            num: num,
+            unit: AngleUnit::None,
+        }
+    }
+
+    /// Converts an angle into the specified unit.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Angle, AngleUnit};
+    ///
+    /// let a = Angle::new(0.5, AngleUnit::Turns).to_unit(AngleUnit::Gradians);
+    /// assert_eq!(a, Angle::new(200.0, AngleUnit::Gradians));
+    /// ```
+    pub fn to_unit(&self, unit: AngleUnit) -> Angle {
+        if self.unit == unit {
+            return *self;
+        }
+
+        Angle::new(self.num / self.unit.turn() * unit.turn(), unit)
+    }
+
+    /// Returns an angle value in degrees.
+    pub fn to_degrees(&self) -> f64 {
+        self.to_unit(AngleUnit::Degrees).num
+    }
+
+    /// Returns an angle value in radians.
+    pub fn to_radians(&self) -> f64 {
+        self.to_unit(AngleUnit::Radians).num
+    }
+
+    /// Compares two angles using fuzzy float compare algorithm.
+    ///
+    /// Angles with different units are compared by their value in degrees.
+    pub fn fuzzy_eq(&self, other: &Angle) -> bool {
+        self.to_degrees().fuzzy_eq(&other.to_degrees())
+    }
+}
+
+/// Parses an angle from the stream, without checking what follows it.
+pub fn parse_angle(s: &mut Stream) -> Result<Angle, ParseError> {
+    s.skip_spaces();
+
+    let n = s.parse_number()?;
+
+    let units: &[(&[u8], AngleUnit)] = &[
+        (b"deg", AngleUnit::Degrees),
+        (b"grad", AngleUnit::Gradians),
+        (b"rad", AngleUnit::Radians),
+        (b"turn", AngleUnit::Turns),
+    ];
+
+    for &(name, unit) in units {
+        if s.starts_with(name) {
+            s.advance(name.len());
+            return Ok(Angle::new(n, unit));
+        }
+    }
+
+    Ok(Angle::new_number(n))
+}
+
+impl_from_str!(Angle);
+
+impl ParseFromSpan for Angle {
+    type Err = ParseError;
+
+    fn from_span(span: StrSpan) -> Result<Angle, ParseError> {
+        let mut s = Stream::from_span(span);
+        let angle = parse_angle(&mut s)?;
+
+        s.skip_spaces();
+        if !s.at_end() {
+            return Err(ParseErrorKind::InvalidAttributeValue(s.gen_error_pos()).into());
+        }
+
+        Ok(angle)
+    }
+}
+
+impl WriteBuffer for Angle {
+    fn write_buf_opt(&self, opt: &WriteOptions, buf: &mut Vec<u8>) {
+        self.num.write_buf_opt(opt, buf);
+
+        let t: &[u8] = match self.unit {
+            AngleUnit::None => b"",
+            AngleUnit::Degrees => b"deg",
+            AngleUnit::Radians => b"rad",
+            AngleUnit::Gradians => b"grad",
+            AngleUnit::Turns => b"turn",
+        };
+
+        buf.extend_from_slice(t);
+    }
+}
+
+impl_display!(Angle);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+
+    macro_rules! test_parse {
+        ($name:ident, $text:expr, $num:expr, $unit:expr) => (
+            #[test]
+            fn $name() {
+                assert_eq!(Angle::from_str($text).unwrap(), Angle::new($num, $unit));
+            }
+        )
+    }
+
+    test_parse!(parse_1, "15", 15.0, AngleUnit::None);
+    test_parse!(parse_2, "15deg", 15.0, AngleUnit::Degrees);
+    test_parse!(parse_3, "-1.5rad", -1.5, AngleUnit::Radians);
+    test_parse!(parse_4, " 100grad ", 100.0, AngleUnit::Gradians);
+    test_parse!(parse_5, "0.25turn", 0.25, AngleUnit::Turns);
+
+    #[test]
+    fn parse_err_1() {
+        assert!(Angle::from_str("").is_err());
+        assert!(Angle::from_str("15px").is_err());
+        assert!(Angle::from_str("15 deg").is_err());
+    }
+
+    #[test]
+    fn write_1() {
+        assert_eq!(Angle::new(15.0, AngleUnit::None).to_string(), "15");
+        assert_eq!(Angle::new(15.0, AngleUnit::Degrees).to_string(), "15deg");
+        assert_eq!(Angle::new(1.5, AngleUnit::Radians).to_string(), "1.5rad");
+        assert_eq!(Angle::new(50.0, AngleUnit::Gradians).to_string(), "50grad");
+        assert_eq!(Angle::new(0.5, AngleUnit::Turns).to_string(), "0.5turn");
+    }
+
+    #[test]
+    fn convert_1() {
+        assert!(Angle::new(180.0, AngleUnit::Degrees).to_radians().fuzzy_eq(&f64::consts::PI));
+        assert!(Angle::new(f64::consts::PI, AngleUnit::Radians).to_degrees().fuzzy_eq(&180.0));
+        assert!(Angle::new(100.0, AngleUnit::Gradians).to_degrees().fuzzy_eq(&90.0));
+        assert!(Angle::new_number(90.0).fuzzy_eq(&Angle::new(0.25, AngleUnit::Turns)));
+    }
+}
@@ -0,0 +1,261 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt;
+use std::str::FromStr;
+
+use svgparser::{
+    Error as ParseError,
+    ErrorKind as ParseErrorKind,
+    Stream,
+    StrSpan,
+};
+
+use {
+    ParseFromSpan,
+    WriteBuffer,
+    WriteOptions,
+    ToStringWithOptions,
+};
+use super::{
+    Rect,
+    Transform,
+};
+
+/// An alignment part of the [`AspectRatio`].
+///
+/// [`AspectRatio`]: struct.AspectRatio.html
+#[derive(Clone,Copy,PartialEq,Debug)]
+#[allow(missing_docs)]
+pub enum Align {
+    None,
+    XMinYMin,
+    XMidYMin,
+    XMaxYMin,
+    XMinYMid,
+    XMidYMid,
+    XMaxYMid,
+    XMinYMax,
+    XMidYMax,
+    XMaxYMax,
+}
+
+static ALIGN_NAMES: &'static [(&'static str, Align)] = &[
+    ("none", Align::None),
+    ("xMinYMin", Align::XMinYMin),
+    ("xMidYMin", Align::XMidYMin),
+    ("xMaxYMin", Align::XMaxYMin),
+    ("xMinYMid", Align::XMinYMid),
+    ("xMidYMid", Align::XMidYMid),
+    ("xMaxYMid", Align::XMaxYMid),
+    ("xMinYMax", Align::XMinYMax),
+    ("xMidYMax", Align::XMidYMax),
+    ("xMaxYMax", Align::XMaxYMax),
+];
+
+impl Align {
+    fn from_name(text: &str) -> Option<Align> {
+        ALIGN_NAMES.iter().find(|&&(name, _)| name == text).map(|&(_, align)| align)
+    }
+
+    fn name(&self) -> &'static str {
+        ALIGN_NAMES.iter().find(|&&(_, align)| align == *self).unwrap().0
+    }
+}
+
+/// Representation of the [`preserveAspectRatio`] attribute value.
+///
+/// [`preserveAspectRatio`]: https://www.w3.org/TR/SVG/coords.html#PreserveAspectRatioAttribute
+#[derive(Clone,Copy,PartialEq,Debug)]
+pub struct AspectRatio {
+    /// The `defer` flag. Used only by the `image` element.
+    pub defer: bool,
+    /// Alignment.
+    pub align: Align,
+    /// `true` for `slice` and `false` for `meet`.
+    pub slice: bool,
+}
+
+impl AspectRatio {
+    /// Calculates a transform that maps the `view_box` into a viewport with the specified size.
+    ///
+    /// Returns `None` if the `view_box` width or height is not positive,
+    /// which disables rendering of the element.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{AspectRatio, Rect, Transform};
+    ///
+    /// let ts = AspectRatio::default().view_box_transform(Rect::new(0.0, 0.0, 10.0, 20.0),
+    ///                                                    40.0, 40.0);
+    /// assert_eq!(ts, Some(Transform::new(2.0, 0.0, 0.0, 2.0, 10.0, 0.0)));
+    /// ```
+    pub fn view_box_transform(&self, view_box: Rect, width: f64, height: f64) -> Option<Transform> {
+        if !(view_box.width > 0.0 && view_box.height > 0.0) {
+            return None;
+        }
+
+        let mut sx = width / view_box.width;
+        let mut sy = height / view_box.height;
+
+        let mut ts = Transform::default();
+
+        if self.align == Align::None {
+            ts.scale(sx, sy);
+            ts.translate(-view_box.x, -view_box.y);
+            return Some(ts);
+        }
+
+        if self.slice {
+            sx = sx.max(sy);
+        } else {
+            sx = sx.min(sy);
+        }
+        sy = sx;
+
+        let dx = width - view_box.width * sx;
+        let dy = height - view_box.height * sy;
+
+        let (tx, ty) = match self.align {
+            Align::None => unreachable!(),
+            Align::XMinYMin => (0.0,      0.0),
+            Align::XMidYMin => (dx / 2.0, 0.0),
+            Align::XMaxYMin => (dx,       0.0),
+            Align::XMinYMid => (0.0,      dy / 2.0),
+            Align::XMidYMid => (dx / 2.0, dy / 2.0),
+            Align::XMaxYMid => (dx,       dy / 2.0),
+            Align::XMinYMax => (0.0,      dy),
+            Align::XMidYMax => (dx / 2.0, dy),
+            Align::XMaxYMax => (dx,       dy),
+        };
+
+        ts.translate(tx, ty);
+        ts.scale(sx, sy);
+        ts.translate(-view_box.x, -view_box.y);
+        Some(ts)
+    }
+}
+
+impl Default for AspectRatio {
+    fn default() -> AspectRatio {
+        AspectRatio {
+            defer: false,
+            align: Align::XMidYMid,
+            slice: false,
+        }
+    }
+}
+
+impl_from_str!(AspectRatio);
+
+impl ParseFromSpan for AspectRatio {
+    type Err = ParseError;
+
+    fn from_span(span: StrSpan) -> Result<AspectRatio, ParseError> {
+        let s = Stream::from_span(span);
+        let err = || -> ParseError {
+            ParseErrorKind::InvalidAttributeValue(s.gen_error_pos()).into()
+        };
+
+        let mut words = span.to_str().split_whitespace().peekable();
+
+        let defer = words.peek() == Some(&"defer");
+        if defer {
+            words.next();
+        }
+
+        let align = match words.next().and_then(Align::from_name) {
+            Some(align) => align,
+            None => return Err(err()),
+        };
+
+        let slice = match words.next() {
+            Some("slice") => true,
+            Some("meet") | None => false,
+            Some(_) => return Err(err()),
+        };
+
+        if words.next().is_some() {
+            return Err(err());
+        }
+
+        Ok(AspectRatio {
+            defer: defer,
+            align: align,
+            slice: slice,
+        })
+    }
+}
+
+impl WriteBuffer for AspectRatio {
+    fn write_buf_opt(&self, _: &WriteOptions, buf: &mut Vec<u8>) {
+        if self.defer {
+            buf.extend_from_slice(b"defer ");
+        }
+
+        buf.extend_from_slice(self.align.name().as_bytes());
+
+        if self.slice {
+            buf.extend_from_slice(b" slice");
+        }
+    }
+}
+
+impl_display!(AspectRatio);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+
+    macro_rules! test_parse {
+        ($name:ident, $text:expr, $result:expr) => (
+            #[test]
+            fn $name() {
+                let v = AspectRatio::from_str($text).unwrap();
+                assert_eq!(v.to_string(), $result);
+            }
+        )
+    }
+
+    test_parse!(parse_1, "xMidYMid", "xMidYMid");
+    test_parse!(parse_2, "xMidYMid meet", "xMidYMid");
+    test_parse!(parse_3, " defer  xMinYMax slice ", "defer xMinYMax slice");
+    test_parse!(parse_4, "none", "none");
+
+    #[test]
+    fn parse_err_1() {
+        assert!(AspectRatio::from_str("").is_err());
+        assert!(AspectRatio::from_str("defer").is_err());
+        assert!(AspectRatio::from_str("xMidYMid clip").is_err());
+        assert!(AspectRatio::from_str("xMidYMid meet slice").is_err());
+    }
+
+    macro_rules! test_ts {
+        ($name:ident, $aspect:expr, $vb:expr, $w:expr, $h:expr, $result:expr) => (
+            #[test]
+            fn $name() {
+                let aspect = AspectRatio::from_str($aspect).unwrap();
+                let ts = aspect.view_box_transform($vb, $w, $h).unwrap();
+                assert!(ts.fuzzy_eq(&$result), "{:?}", ts);
+            }
+        )
+    }
+
+    test_ts!(view_box_ts_1, "xMidYMid", Rect::new(0.0, 0.0, 100.0, 50.0), 200.0, 200.0,
+             Transform::new(2.0, 0.0, 0.0, 2.0, 0.0, 50.0));
+    test_ts!(view_box_ts_2, "xMaxYMax slice", Rect::new(0.0, 0.0, 100.0, 50.0), 200.0, 200.0,
+             Transform::new(4.0, 0.0, 0.0, 4.0, -200.0, 0.0));
+    test_ts!(view_box_ts_3, "none", Rect::new(10.0, 20.0, 100.0, 50.0), 200.0, 200.0,
+             Transform::new(2.0, 0.0, 0.0, 4.0, -20.0, -80.0));
+    test_ts!(view_box_ts_4, "xMinYMin", Rect::new(10.0, 20.0, 100.0, 50.0), 200.0, 200.0,
+             Transform::new(2.0, 0.0, 0.0, 2.0, -20.0, -40.0));
+
+    #[test]
+    fn view_box_ts_5() {
+        let aspect = AspectRatio::default();
+        assert_eq!(aspect.view_box_transform(Rect::new(0.0, 0.0, 0.0, 50.0), 200.0, 200.0), None);
+        assert_eq!(aspect.view_box_transform(Rect::new(0.0, 0.0, 100.0, 0.0), 200.0, 200.0), None);
+    }
+}
@@ -8,7 +8,10 @@ use std::str::FromStr;
 use svgparser::{
     Color as ParserColor,
     Error as ParseError,
+    ErrorKind as ParseErrorKind,
+    Stream,
     StrSpan,
+    StreamExt,
 };
 
 use {
@@ -17,6 +20,7 @@ use {
     WriteOptions,
     ToStringWithOptions,
 };
+use super::angle::parse_angle;
 
 /// Representation of the [`<color>`] type.
 ///
@@ -29,13 +33,27 @@ pub struct Color {
     pub green: u8,
     #[allow(missing_docs)]
     pub blue: u8,
+    /// Alpha channel. `255` is fully opaque.
+    pub alpha: u8,
 }
 
 impl Color {
-    /// Constructs a new color.
+    /// Constructs a new opaque color.
     #[inline]
     pub fn new(red: u8, green: u8, blue: u8) -> Color {
-        Color { red: red, green: green, blue: blue }
+        Color::new_rgba(red, green, blue, 255)
+    }
+
+    /// Constructs a new color with an alpha channel.
+    #[inline]
+    pub fn new_rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Color {
+        Color { red: red, green: green, blue: blue, alpha: alpha }
+    }
+
+    /// Returns `true` if the color is fully opaque.
+    #[inline]
+    pub fn is_opaque(&self) -> bool {
+        self.alpha == 255
     }
 }
 
@@ -44,12 +62,197 @@ impl_from_str!(Color);
 impl ParseFromSpan for Color {
     type Err = ParseError;
 
+    /// Parses a color.
+    ///
+    /// In addition to the SVG 1.1 syntax, supports `#rgba`, `#rrggbbaa`,
+    /// `rgba()`, `hsl()`, `hsla()` and `transparent` from CSS Color Module Level 4.
     fn from_span(span: StrSpan) -> Result<Color, ParseError> {
-        let c = ParserColor::from_span(span)?;
-        Ok(Color::new(c.red, c.green, c.blue))
+        let mut s = Stream::from_span(span);
+        s.skip_spaces();
+
+        let start = s.pos();
+
+        let color = if s.is_curr_byte_eq(b'#') {
+            s.advance(1);
+            let hex = s.consume_bytes(|_, c| (c as char).is_digit(16)).to_str().as_bytes();
+            match hex.len() {
+                3 | 4 => {
+                    // #rgb or #rgba
+                    let alpha = if hex.len() == 4 { short_hex(hex[3]) } else { 255 };
+                    Color::new_rgba(short_hex(hex[0]), short_hex(hex[1]), short_hex(hex[2]),
+                                    alpha)
+                }
+                6 | 8 => {
+                    // #rrggbb or #rrggbbaa
+                    let alpha = if hex.len() == 8 { hex_pair(hex[6], hex[7]) } else { 255 };
+                    Color::new_rgba(hex_pair(hex[0], hex[1]), hex_pair(hex[2], hex[3]),
+                                    hex_pair(hex[4], hex[5]), alpha)
+                }
+                _ => {
+                    return Err(ParseErrorKind::InvalidColor(s.gen_error_pos_from(start)).into());
+                }
+            }
+        } else {
+            let name = s.consume_name()?;
+            if s.is_curr_byte_eq(b'(') {
+                s.advance(1);
+                match name.to_str() {
+                    "rgb" | "rgba" => parse_rgb(&mut s)?,
+                    "hsl" | "hsla" => parse_hsl(&mut s)?,
+                    _ => {
+                        let pos = s.gen_error_pos_from(start);
+                        return Err(ParseErrorKind::InvalidColor(pos).into());
+                    }
+                }
+            } else if name.to_str() == "transparent" {
+                Color::new_rgba(0, 0, 0, 0)
+            } else {
+                let c = ParserColor::from_span(name)?;
+                Color::new(c.red, c.green, c.blue)
+            }
+        };
+
+        s.skip_spaces();
+        if !s.at_end() {
+            return Err(ParseErrorKind::InvalidColor(s.gen_error_pos()).into());
+        }
+
+        Ok(color)
+    }
+}
+
+// Parses function arguments after the opening bracket.
+//
+// Both the legacy comma-separated and the space-separated syntax are supported.
+// Returns the number of parsed arguments.
+fn parse_args(s: &mut Stream, hue: bool, args: &mut [(f64, bool); 4])
+    -> Result<usize, ParseError>
+{
+    let start = s.pos();
+    let mut count = 0;
+    loop {
+        s.skip_spaces();
+
+        if s.is_curr_byte_eq(b')') {
+            s.advance(1);
+            break;
+        }
+
+        if count == args.len() {
+            return Err(ParseErrorKind::InvalidColor(s.gen_error_pos_from(start)).into());
+        }
+
+        args[count] = if count == 0 && hue {
+            (parse_angle(s)?.to_degrees(), false)
+        } else {
+            let n = s.parse_number()?;
+            if s.is_curr_byte_eq(b'%') {
+                s.advance(1);
+                (n, true)
+            } else {
+                (n, false)
+            }
+        };
+        count += 1;
+
+        s.skip_spaces();
+        if s.is_curr_byte_eq(b',') || s.is_curr_byte_eq(b'/') {
+            s.advance(1);
+        }
+    }
+
+    if count < 3 {
+        return Err(ParseErrorKind::InvalidColor(s.gen_error_pos_from(start)).into());
+    }
+
+    Ok(count)
+}
+
+fn parse_alpha(args: &[(f64, bool); 4], count: usize) -> u8 {
+    if count < 4 {
+        return 255;
+    }
+
+    let (n, percent) = args[3];
+    let n = if percent { n / 100.0 } else { n };
+    to_u8(n * 255.0)
+}
+
+fn parse_rgb(s: &mut Stream) -> Result<Color, ParseError> {
+    let mut args = [(0.0, false); 4];
+    let count = parse_args(s, false, &mut args)?;
+
+    let channel = |(n, percent): (f64, bool)| {
+        if percent { to_u8(n * 255.0 / 100.0) } else { to_u8(n) }
+    };
+
+    Ok(Color::new_rgba(channel(args[0]), channel(args[1]), channel(args[2]),
+                       parse_alpha(&args, count)))
+}
+
+fn parse_hsl(s: &mut Stream) -> Result<Color, ParseError> {
+    let mut args = [(0.0, false); 4];
+    let count = parse_args(s, true, &mut args)?;
+
+    let hue = ((args[0].0 % 360.0) + 360.0) % 360.0 / 360.0;
+    let saturation = (args[1].0 / 100.0).max(0.0).min(1.0);
+    let lightness = (args[2].0 / 100.0).max(0.0).min(1.0);
+
+    let t2 = if lightness <= 0.5 {
+        lightness * (saturation + 1.0)
+    } else {
+        lightness + saturation - lightness * saturation
+    };
+    let t1 = lightness * 2.0 - t2;
+
+    let red = hue_to_rgb(t1, t2, hue + 1.0 / 3.0);
+    let green = hue_to_rgb(t1, t2, hue);
+    let blue = hue_to_rgb(t1, t2, hue - 1.0 / 3.0);
+
+    Ok(Color::new_rgba(to_u8(red * 255.0), to_u8(green * 255.0), to_u8(blue * 255.0),
+                       parse_alpha(&args, count)))
+}
+
+fn hue_to_rgb(t1: f64, t2: f64, mut hue: f64) -> f64 {
+    if hue < 0.0 {
+        hue += 1.0;
+    }
+    if hue > 1.0 {
+        hue -= 1.0;
+    }
+
+    if hue * 6.0 < 1.0 {
+        t1 + (t2 - t1) * hue * 6.0
+    } else if hue * 2.0 < 1.0 {
+        t2
+    } else if hue * 3.0 < 2.0 {
+        t1 + (t2 - t1) * (2.0 / 3.0 - hue) * 6.0
+    } else {
+        t1
     }
 }
 
+#[inline]
+fn to_u8(n: f64) -> u8 {
+    n.round().max(0.0).min(255.0) as u8
+}
+
+#[inline]
+fn from_hex(c: u8) -> u8 {
+    (c as char).to_digit(16).unwrap_or(0) as u8
+}
+
+#[inline]
+fn short_hex(c: u8) -> u8 {
+    let h = from_hex(c);
+    (h << 4) | h
+}
+
+#[inline]
+fn hex_pair(c1: u8, c2: u8) -> u8 {
+    (from_hex(c1) << 4) | from_hex(c2)
+}
+
 static CHARS: &'static [u8] = b"0123456789abcdef";
 
 #[inline]
@@ -57,20 +260,81 @@ fn int2hex(n: u8) -> (u8, u8) {
     (CHARS[(n >> 4) as usize], CHARS[(n & 0xf) as usize])
 }
 
+// Only keywords that can be shorter than the `#rrggbb` notation are listed.
+// If a color has more than one name, the first one is used.
+static COLOR_NAMES: &'static [(&'static str, u8, u8, u8)] = &[
+    ("aqua",    0,   255, 255),
+    ("azure",   240, 255, 255),
+    ("beige",   245, 245, 220),
+    ("bisque",  255, 228, 196),
+    ("black",   0,   0,   0),
+    ("blue",    0,   0,   255),
+    ("brown",   165, 42,  42),
+    ("coral",   255, 127, 80),
+    ("gold",    255, 215, 0),
+    ("gray",    128, 128, 128),
+    ("green",   0,   128, 0),
+    ("indigo",  75,  0,   130),
+    ("ivory",   255, 255, 240),
+    ("khaki",   240, 230, 140),
+    ("lime",    0,   255, 0),
+    ("linen",   250, 240, 230),
+    ("maroon",  128, 0,   0),
+    ("navy",    0,   0,   128),
+    ("olive",   128, 128, 0),
+    ("orange",  255, 165, 0),
+    ("orchid",  218, 112, 214),
+    ("peru",    205, 133, 63),
+    ("pink",    255, 192, 203),
+    ("plum",    221, 160, 221),
+    ("purple",  128, 0,   128),
+    ("red",     255, 0,   0),
+    ("salmon",  250, 128, 114),
+    ("sienna",  160, 82,  45),
+    ("silver",  192, 192, 192),
+    ("snow",    255, 250, 250),
+    ("tan",     210, 180, 140),
+    ("teal",    0,   128, 128),
+    ("tomato",  255, 99,  71),
+    ("violet",  238, 130, 238),
+    ("wheat",   245, 222, 179),
+    ("white",   255, 255, 255),
+    ("yellow",  255, 255, 0),
+];
+
 impl WriteBuffer for Color {
     fn write_buf_opt(&self, opt: &WriteOptions, buf: &mut Vec<u8>) {
-        // TODO: rgb() support
-        // TODO: color name support
-
-        buf.push(b'#');
         let (r1, r2) = int2hex(self.red);
         let (g1, g2) = int2hex(self.green);
         let (b1, b2) = int2hex(self.blue);
+        let (a1, a2) = int2hex(self.alpha);
+
+        let is_short = r1 == r2 && g1 == g2 && b1 == b2 && (self.is_opaque() || a1 == a2);
+        let trim = opt.trim_hex_colors && is_short;
+
+        if opt.use_color_keywords && self.is_opaque() {
+            let hex_len = if trim { 4 } else { 7 };
+            let name = COLOR_NAMES.iter().find(|&&(_, r, g, b)| {
+                r == self.red && g == self.green && b == self.blue
+            });
 
-        if opt.trim_hex_colors && r1 == r2 && g1 == g2 && b1 == b2 {
+            if let Some(&(name, _, _, _)) = name {
+                if name.len() < hex_len {
+                    buf.extend_from_slice(name.as_bytes());
+                    return;
+                }
+            }
+        }
+
+        buf.push(b'#');
+        if trim {
             buf.push(r1);
             buf.push(g1);
             buf.push(b1);
+
+            if !self.is_opaque() {
+                buf.push(a1);
+            }
         } else {
             buf.push(r1);
             buf.push(r2);
@@ -78,6 +342,11 @@ impl WriteBuffer for Color {
             buf.push(g2);
             buf.push(b1);
             buf.push(b2);
+
+            if !self.is_opaque() {
+                buf.push(a1);
+                buf.push(a2);
+            }
         }
     }
 }
@@ -86,6 +355,7 @@ impl_display!(Color);
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
     use super::*;
     use {WriteOptions, WriteBuffer};
 
@@ -106,4 +376,68 @@ mod tests {
     test_color!(gen_color_2, Color::new(255, 127, 5), false, "#ff7f05");
     test_color!(gen_color_3, Color::new(255, 0, 0), true, "#f00");
     test_color!(gen_color_4, Color::new(255, 127, 5), true, "#ff7f05");
+    test_color!(gen_color_5, Color::new_rgba(255, 0, 0, 128), false, "#ff000080");
+    test_color!(gen_color_6, Color::new_rgba(255, 0, 0, 0), true, "#f000");
+    test_color!(gen_color_7, Color::new_rgba(255, 0, 0, 128), true, "#ff000080");
+
+    macro_rules! test_keyword {
+        ($name:ident, $c:expr, $trim:expr, $result:expr) => (
+            #[test]
+            fn $name() {
+                let mut opt = WriteOptions::default();
+                opt.trim_hex_colors = $trim;
+                opt.use_color_keywords = true;
+                let mut out = Vec::new();
+                $c.write_buf_opt(&opt, &mut out);
+                assert_eq!(String::from_utf8(out).unwrap(), $result);
+            }
+        )
+    }
+
+    test_keyword!(gen_keyword_1, Color::new(255, 0, 0), true, "red");
+    test_keyword!(gen_keyword_2, Color::new(255, 255, 255), true, "#fff");
+    test_keyword!(gen_keyword_3, Color::new(255, 255, 255), false, "white");
+    test_keyword!(gen_keyword_4, Color::new(128, 128, 128), true, "gray");
+    test_keyword!(gen_keyword_5, Color::new(255, 99, 71), true, "tomato");
+    test_keyword!(gen_keyword_6, Color::new_rgba(255, 0, 0, 128), true, "#ff000080");
+    test_keyword!(gen_keyword_7, Color::new(1, 2, 3), true, "#010203");
+
+    macro_rules! test_parse {
+        ($name:ident, $text:expr, $result:expr) => (
+            #[test]
+            fn $name() {
+                assert_eq!(Color::from_str($text).unwrap(), $result);
+            }
+        )
+    }
+
+    test_parse!(parse_1, "#ff0000", Color::new(255, 0, 0));
+    test_parse!(parse_2, "#f00", Color::new(255, 0, 0));
+    test_parse!(parse_3, "#f008", Color::new_rgba(255, 0, 0, 136));
+    test_parse!(parse_4, "#FF000080", Color::new_rgba(255, 0, 0, 128));
+    test_parse!(parse_5, "red", Color::new(255, 0, 0));
+    test_parse!(parse_6, " transparent ", Color::new_rgba(0, 0, 0, 0));
+    test_parse!(parse_7, "rgb(255, 127, 0)", Color::new(255, 127, 0));
+    test_parse!(parse_8, "rgb(100%, 50%, 0%)", Color::new(255, 128, 0));
+    test_parse!(parse_9, "rgba(255, 0, 0, 0.5)", Color::new_rgba(255, 0, 0, 128));
+    test_parse!(parse_10, "rgb(255 0 0 / 50%)", Color::new_rgba(255, 0, 0, 128));
+    test_parse!(parse_11, "rgb(300, -5, 0)", Color::new(255, 0, 0));
+    test_parse!(parse_12, "hsl(0, 100%, 50%)", Color::new(255, 0, 0));
+    test_parse!(parse_13, "hsl(120, 100%, 25%)", Color::new(0, 128, 0));
+    test_parse!(parse_14, "hsla(240, 100%, 50%, 0)", Color::new_rgba(0, 0, 255, 0));
+    test_parse!(parse_15, "hsl(0.5turn 100% 50%)", Color::new(0, 255, 255));
+    test_parse!(parse_16, "hsl(-120, 100%, 50%)", Color::new(0, 0, 255));
+
+    #[test]
+    fn parse_err_1() {
+        assert!(Color::from_str("").is_err());
+        assert!(Color::from_str("#ff").is_err());
+        assert!(Color::from_str("#fffff").is_err());
+        assert!(Color::from_str("qwe").is_err());
+        assert!(Color::from_str("rgb(255, 0)").is_err());
+        assert!(Color::from_str("rgb(255, 0, 0, 1, 1)").is_err());
+        assert!(Color::from_str("rgb(255, 0, 0").is_err());
+        assert!(Color::from_str("cmyk(0, 0, 0)").is_err());
+        assert!(Color::from_str("red blue").is_err());
+    }
 }
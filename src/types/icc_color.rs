@@ -0,0 +1,148 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt;
+use std::str::FromStr;
+
+use svgparser::{
+    Error as ParseError,
+    ErrorKind as ParseErrorKind,
+    Stream,
+    StrSpan,
+    StreamExt,
+};
+
+use {
+    ListSeparator,
+    ParseFromSpan,
+    WriteBuffer,
+    WriteOptions,
+    ToStringWithOptions,
+};
+use super::Color;
+
+/// Representation of the [`<icccolor>`] type alongside its sRGB fallback.
+///
+/// Like `#cd853f icc-color(acmecmyk, 0.11, 0.48, 0.83, 0.00)`.
+///
+/// [`<icccolor>`]: https://www.w3.org/TR/SVG/types.html#DataTypeICCColor
+#[derive(Clone,PartialEq,Debug)]
+pub struct IccColor {
+    /// sRGB fallback color.
+    pub color: Color,
+    /// Color profile name.
+    pub profile: String,
+    /// Color components in the color profile space.
+    pub components: Vec<f64>,
+}
+
+impl_from_str!(IccColor);
+
+impl ParseFromSpan for IccColor {
+    type Err = ParseError;
+
+    fn from_span(span: StrSpan) -> Result<IccColor, ParseError> {
+        let idx = match span.to_str().find("icc-color(") {
+            Some(idx) => idx,
+            None => {
+                let s = Stream::from_span(span);
+                return Err(ParseErrorKind::InvalidColor(s.gen_error_pos()).into());
+            }
+        };
+
+        let color = Color::from_span(span.slice_region(0, idx))?;
+
+        let mut s = Stream::from_span(span.slice_region(idx, span.len()));
+        s.advance(10); // icc-color(
+        s.skip_spaces();
+        let profile = s.consume_name()?.to_str().to_string();
+
+        let mut components = Vec::new();
+        loop {
+            s.skip_spaces();
+            if s.is_curr_byte_eq(b')') {
+                s.advance(1);
+                break;
+            }
+
+            if s.is_curr_byte_eq(b',') {
+                s.advance(1);
+            }
+
+            components.push(s.parse_number()?);
+        }
+
+        s.skip_spaces();
+        if components.is_empty() || !s.at_end() {
+            return Err(ParseErrorKind::InvalidColor(s.gen_error_pos()).into());
+        }
+
+        Ok(IccColor {
+            color: color,
+            profile: profile,
+            components: components,
+        })
+    }
+}
+
+impl WriteBuffer for IccColor {
+    fn write_buf_opt(&self, opt: &WriteOptions, buf: &mut Vec<u8>) {
+        self.color.write_buf_opt(opt, buf);
+
+        buf.extend_from_slice(b" icc-color(");
+        buf.extend_from_slice(self.profile.as_bytes());
+        for n in &self.components {
+            match opt.list_separator {
+                ListSeparator::Space => buf.push(b' '),
+                ListSeparator::Comma => buf.push(b','),
+                ListSeparator::CommaSpace => buf.extend_from_slice(b", "),
+            }
+
+            n.write_buf_opt(opt, buf);
+        }
+        buf.push(b')');
+    }
+}
+
+impl_display!(IccColor);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+
+    #[test]
+    fn parse_1() {
+        let c = IccColor::from_str("#CD853F icc-color(acmecmyk, 0.11, 0.48, 0.83, 0.00)").unwrap();
+        assert_eq!(c.color, Color::new(205, 133, 63));
+        assert_eq!(c.profile, "acmecmyk");
+        assert_eq!(c.components, vec![0.11, 0.48, 0.83, 0.0]);
+    }
+
+    #[test]
+    fn parse_2() {
+        let c = IccColor::from_str("red icc-color(p 1 .5)").unwrap();
+        assert_eq!(c.color, Color::new(255, 0, 0));
+        assert_eq!(c.components, vec![1.0, 0.5]);
+    }
+
+    #[test]
+    fn parse_err_1() {
+        assert!(IccColor::from_str("red").is_err());
+        assert!(IccColor::from_str("icc-color(p, 1)").is_err());
+        assert!(IccColor::from_str("red icc-color(p)").is_err());
+        assert!(IccColor::from_str("red icc-color(p, 1").is_err());
+        assert!(IccColor::from_str("red icc-color(p, 1) blue").is_err());
+    }
+
+    #[test]
+    fn write_1() {
+        let c = IccColor::from_str("#CD853F icc-color(acmecmyk, 0.11, 0.48, 0.83, 0.00)").unwrap();
+        assert_eq!(c.to_string(), "#cd853f icc-color(acmecmyk 0.11 0.48 0.83 0)");
+
+        let mut opt = WriteOptions::default();
+        opt.list_separator = ListSeparator::CommaSpace;
+        assert_eq!(c.to_string_with_opt(&opt), "#cd853f icc-color(acmecmyk, 0.11, 0.48, 0.83, 0)");
+    }
+}
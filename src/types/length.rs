@@ -63,6 +63,106 @@ impl Length {
     }
 }
 
+impl Length {
+    /// Converts a length into user units.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::{Length, LengthUnit, ConversionContext};
+    ///
+    /// let ctx = ConversionContext::default();
+    /// assert_eq!(Length::new(1.0, LengthUnit::In).to_user_units(&ctx), 96.0);
+    /// assert_eq!(Length::new(2.0, LengthUnit::Em).to_user_units(&ctx), 32.0);
+    /// ```
+    pub fn to_user_units(&self, ctx: &ConversionContext) -> f64 {
+        let n = self.num;
+        match self.unit {
+              LengthUnit::None
+            | LengthUnit::Px => n,
+            LengthUnit::Em => n * ctx.font_size,
+            LengthUnit::Ex => n * ctx.font_size / 2.0,
+            LengthUnit::In => n * ctx.dpi,
+            LengthUnit::Cm => n * ctx.dpi / 2.54,
+            LengthUnit::Mm => n * ctx.dpi / 25.4,
+            LengthUnit::Pt => n * ctx.dpi / 72.0,
+            LengthUnit::Pc => n * ctx.dpi / 6.0,
+            LengthUnit::Percent => {
+                let base = match ctx.direction {
+                    LengthDirection::Horizontal => ctx.viewport_width,
+                    LengthDirection::Vertical => ctx.viewport_height,
+                    LengthDirection::Diagonal => {
+                        let w = ctx.viewport_width;
+                        let h = ctx.viewport_height;
+                        ((w * w + h * h) / 2.0).sqrt()
+                    }
+                    LengthDirection::FontSize => ctx.font_size,
+                };
+
+                n * base / 100.0
+            }
+        }
+    }
+}
+
+/// A viewport dimension that percentage lengths are relative to.
+#[derive(Clone,Copy,PartialEq,Debug)]
+pub enum LengthDirection {
+    /// Relative to the viewport width. Used by `x`, `width`, `cx`, etc.
+    Horizontal,
+    /// Relative to the viewport height. Used by `y`, `height`, `cy`, etc.
+    Vertical,
+    /// Relative to the normalized viewport diagonal. Used by `r`, `stroke-width`, etc.
+    Diagonal,
+    /// Relative to the font size. Used by `font-size`.
+    FontSize,
+}
+
+/// Data needed to convert a [`Length`] into user units.
+///
+/// Use [`Node::conversion_context`] to build it for a specific attribute.
+///
+/// [`Length`]: struct.Length.html
+/// [`Node::conversion_context`]: struct.Node.html#method.conversion_context
+#[derive(Clone,Copy,PartialEq,Debug)]
+pub struct ConversionContext {
+    /// The width of the nearest viewport in user units.
+    ///
+    /// Default: 100
+    pub viewport_width: f64,
+
+    /// The height of the nearest viewport in user units.
+    ///
+    /// Default: 100
+    pub viewport_height: f64,
+
+    /// Which viewport dimension percentage lengths are relative to.
+    ///
+    /// Default: `LengthDirection::Diagonal`
+    pub direction: LengthDirection,
+
+    /// The resolved `font-size` in user units. Used by `em` and `ex` units.
+    ///
+    /// Default: 16
+    pub font_size: f64,
+
+    /// Number of user units in an inch.
+    ///
+    /// Default: 96
+    pub dpi: f64,
+}
+
+impl Default for ConversionContext {
+    fn default() -> ConversionContext {
+        ConversionContext {
+            viewport_width: 100.0,
+            viewport_height: 100.0,
+            direction: LengthDirection::Diagonal,
+            font_size: 16.0,
+            dpi: 96.0,
+        }
+    }
+}
+
 impl_from_str!(Length);
 
 impl ParseFromSpan for Length {
@@ -102,6 +202,7 @@ impl_display!(Length);
 mod tests {
     use super::*;
     use types::LengthUnit;
+    use FuzzyEq;
 
     macro_rules! test_length {
         ($name:ident, $len:expr, $unit:expr, $result:expr) => (
@@ -123,4 +224,32 @@ mod tests {
     test_length!(gen_length_8,  1.0, LengthUnit::Pt, "1pt");
     test_length!(gen_length_9,  1.0, LengthUnit::Pc, "1pc");
     test_length!(gen_length_10, 1.0, LengthUnit::Percent, "1%");
+
+    macro_rules! test_convert {
+        ($name:ident, $len:expr, $unit:expr, $direction:expr, $result:expr) => (
+            #[test]
+            fn $name() {
+                let mut ctx = ConversionContext::default();
+                ctx.viewport_width = 200.0;
+                ctx.viewport_height = 100.0;
+                ctx.direction = $direction;
+                let n = Length::new($len, $unit).to_user_units(&ctx);
+                assert!(n.fuzzy_eq(&$result), "{} != {}", n, $result);
+            }
+        )
+    }
+
+    test_convert!(convert_1, 10.0, LengthUnit::None, LengthDirection::Diagonal, 10.0);
+    test_convert!(convert_2, 10.0, LengthUnit::Px, LengthDirection::Diagonal, 10.0);
+    test_convert!(convert_3, 1.0, LengthUnit::In, LengthDirection::Diagonal, 96.0);
+    test_convert!(convert_4, 2.54, LengthUnit::Cm, LengthDirection::Diagonal, 96.0);
+    test_convert!(convert_5, 25.4, LengthUnit::Mm, LengthDirection::Diagonal, 96.0);
+    test_convert!(convert_6, 72.0, LengthUnit::Pt, LengthDirection::Diagonal, 96.0);
+    test_convert!(convert_7, 6.0, LengthUnit::Pc, LengthDirection::Diagonal, 96.0);
+    test_convert!(convert_8, 2.0, LengthUnit::Em, LengthDirection::Diagonal, 32.0);
+    test_convert!(convert_9, 2.0, LengthUnit::Ex, LengthDirection::Diagonal, 16.0);
+    test_convert!(convert_10, 50.0, LengthUnit::Percent, LengthDirection::Horizontal, 100.0);
+    test_convert!(convert_11, 50.0, LengthUnit::Percent, LengthDirection::Vertical, 50.0);
+    test_convert!(convert_12, 100.0, LengthUnit::Percent, LengthDirection::Diagonal,
+                  25000f64.sqrt());
 }
@@ -4,9 +4,35 @@
 
 //! This module contains submodules which represent SVG value types.
 
-pub use self::transform::Transform;
+pub use self::angle::{
+    Angle,
+    AngleUnit,
+};
+pub use self::aspect_ratio::{
+    Align,
+    AspectRatio,
+};
+pub use self::points::Points;
+pub use self::rect::Rect;
+pub use self::transform::{
+    DecomposedTransform,
+    Transform,
+};
+pub use self::transform_list::{
+    TransformList,
+    TransformListItem,
+};
 pub use self::color::Color;
-pub use self::length::Length;
+pub use self::icc_color::IccColor;
+pub use self::length::{
+    ConversionContext,
+    Length,
+    LengthDirection,
+};
+pub use self::paint_order::{
+    PaintOrder,
+    PaintOrderKind,
+};
 pub use self::number::{FuzzyEq, FuzzyOrd};
 
 pub use svgparser::LengthUnit;
@@ -22,12 +48,21 @@ use {
 pub type NumberList = Vec<f64>;
 /// Representation of the `<list-of-lengths>` type.
 pub type LengthList = Vec<Length>;
+/// Representation of the `<list-of-angles>` type.
+pub type AngleList = Vec<Angle>;
 
 pub mod path;
+mod angle;
+mod aspect_ratio;
 mod color;
+mod icc_color;
 mod length;
 mod number;
+mod paint_order;
+mod points;
+mod rect;
 mod transform;
+mod transform_list;
 
 
 impl WriteBuffer for NumberList {
@@ -42,10 +77,17 @@ impl WriteBuffer for LengthList {
     }
 }
 
+impl WriteBuffer for AngleList {
+    fn write_buf_opt(&self, opt: &WriteOptions, buf: &mut Vec<u8>) {
+        write_list(self, opt, buf);
+    }
+}
+
 // We can't use `impl_display` macro, because the `Display` trait
 // can't be implement for a std type.
 impl ToStringWithOptions for NumberList {}
 impl ToStringWithOptions for LengthList {}
+impl ToStringWithOptions for AngleList {}
 
 fn write_list<T: WriteBuffer>(list: &[T], opt: &WriteOptions, buf: &mut Vec<u8>) {
     for (n, l) in list.iter().enumerate() {
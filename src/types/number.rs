@@ -61,7 +61,12 @@ pub fn write_num(num: &f64, rm_leading_zero: bool, buf: &mut Vec<u8>) {
     // to prevent writing ugly numbers like 29.999999999999996.
     // It's not 100% correct, but differences are insignificant.
 
-    let v = (num * 100_000_000_000.0).round() / 100_000_000_000.0;
+    let mut v = (num * 100_000_000_000.0).round() / 100_000_000_000.0;
+
+    // Rust prints a negative zero as '-0', which is not what we want.
+    if v == 0.0 {
+        v = 0.0;
+    }
 
     let start_pos = buf.len();
 
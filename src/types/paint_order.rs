@@ -0,0 +1,195 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt;
+use std::str::FromStr;
+
+use svgparser::{
+    Error as ParseError,
+    ErrorKind as ParseErrorKind,
+    Stream,
+    StrSpan,
+};
+
+use {
+    ParseFromSpan,
+    WriteBuffer,
+    WriteOptions,
+    ToStringWithOptions,
+};
+
+/// A part of the [`PaintOrder`].
+///
+/// [`PaintOrder`]: struct.PaintOrder.html
+#[derive(Clone,Copy,PartialEq,Debug)]
+#[allow(missing_docs)]
+pub enum PaintOrderKind {
+    Fill,
+    Stroke,
+    Markers,
+}
+
+impl PaintOrderKind {
+    fn name(&self) -> &'static str {
+        match *self {
+            PaintOrderKind::Fill => "fill",
+            PaintOrderKind::Stroke => "stroke",
+            PaintOrderKind::Markers => "markers",
+        }
+    }
+}
+
+/// Representation of the [`paint-order`] attribute value.
+///
+/// Always contains all three parts, so omitted ones are stored in the default order.
+///
+/// [`paint-order`]: https://www.w3.org/TR/SVG2/painting.html#PaintOrder
+#[derive(Clone,Copy,PartialEq,Debug)]
+pub struct PaintOrder {
+    /// Parts in painting order.
+    pub order: [PaintOrderKind; 3],
+}
+
+static DEFAULT_ORDER: [PaintOrderKind; 3] = [
+    PaintOrderKind::Fill,
+    PaintOrderKind::Stroke,
+    PaintOrderKind::Markers,
+];
+
+impl PaintOrder {
+    /// Constructs a new paint order.
+    ///
+    /// Parts that are not present in `list` will be appended in the default order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `list` contains duplicates.
+    pub fn new(list: &[PaintOrderKind]) -> PaintOrder {
+        for (i, kind) in list.iter().enumerate() {
+            assert!(!list[i + 1..].contains(kind), "duplicated paint order part");
+        }
+
+        let mut order = DEFAULT_ORDER;
+        let mut idx = 0;
+        for kind in list.iter().chain(DEFAULT_ORDER.iter()) {
+            if !order[..idx].contains(kind) {
+                order[idx] = *kind;
+                idx += 1;
+            }
+        }
+
+        PaintOrder {
+            order: order,
+        }
+    }
+
+    /// Returns `true` if the paint order is `normal`, aka `fill stroke markers`.
+    pub fn is_normal(&self) -> bool {
+        self.order == DEFAULT_ORDER
+    }
+}
+
+impl Default for PaintOrder {
+    fn default() -> PaintOrder {
+        PaintOrder {
+            order: DEFAULT_ORDER,
+        }
+    }
+}
+
+impl_from_str!(PaintOrder);
+
+impl ParseFromSpan for PaintOrder {
+    type Err = ParseError;
+
+    fn from_span(span: StrSpan) -> Result<PaintOrder, ParseError> {
+        let s = Stream::from_span(span);
+        let err = || -> ParseError {
+            ParseErrorKind::InvalidAttributeValue(s.gen_error_pos()).into()
+        };
+
+        let text = span.to_str().trim();
+        if text == "normal" {
+            return Ok(PaintOrder::default());
+        }
+
+        let mut list = Vec::with_capacity(3);
+        for name in text.split_whitespace() {
+            let kind = match name {
+                "fill" => PaintOrderKind::Fill,
+                "stroke" => PaintOrderKind::Stroke,
+                "markers" => PaintOrderKind::Markers,
+                _ => return Err(err()),
+            };
+
+            if list.contains(&kind) {
+                return Err(err());
+            }
+
+            list.push(kind);
+        }
+
+        if list.is_empty() {
+            return Err(err());
+        }
+
+        Ok(PaintOrder::new(&list))
+    }
+}
+
+impl WriteBuffer for PaintOrder {
+    fn write_buf_opt(&self, _: &WriteOptions, buf: &mut Vec<u8>) {
+        if self.is_normal() {
+            buf.extend_from_slice(b"normal");
+            return;
+        }
+
+        // Write only the parts that are not in the default order.
+        let mut len = 3;
+        while len > 1 && PaintOrder::new(&self.order[..len - 1]) == *self {
+            len -= 1;
+        }
+
+        for (i, kind) in self.order[..len].iter().enumerate() {
+            if i != 0 {
+                buf.push(b' ');
+            }
+
+            buf.extend_from_slice(kind.name().as_bytes());
+        }
+    }
+}
+
+impl_display!(PaintOrder);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+
+    macro_rules! test_parse {
+        ($name:ident, $text:expr, $result:expr) => (
+            #[test]
+            fn $name() {
+                let order = PaintOrder::from_str($text).unwrap();
+                assert_eq!(order.to_string(), $result);
+            }
+        )
+    }
+
+    test_parse!(parse_1, "normal", "normal");
+    test_parse!(parse_2, "fill stroke markers", "normal");
+    test_parse!(parse_3, "fill", "normal");
+    test_parse!(parse_4, "stroke", "stroke");
+    test_parse!(parse_5, "stroke fill", "stroke");
+    test_parse!(parse_6, "markers stroke", "markers stroke");
+    test_parse!(parse_7, "  fill  markers ", "fill markers");
+
+    #[test]
+    fn parse_err_1() {
+        assert!(PaintOrder::from_str("").is_err());
+        assert!(PaintOrder::from_str("fill fill").is_err());
+        assert!(PaintOrder::from_str("fill none").is_err());
+    }
+}
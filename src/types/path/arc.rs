@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::f64;
+
+use types::FuzzyEq;
+
+/// An elliptical arc in the center parameterization.
+///
+/// Points calculated from the center parameterization are not exact,
+/// so the original end point should be used instead of the calculated one
+/// to prevent precision loss.
+///
+/// https://www.w3.org/TR/SVG/implnote.html#ArcConversionEndpointToCenter
+#[derive(Clone,Copy,Debug)]
+pub struct CenterArc {
+    pub cx: f64,
+    pub cy: f64,
+    pub rx: f64,
+    pub ry: f64,
+    /// X-axis rotation in radians.
+    pub phi: f64,
+    /// Start angle in radians.
+    pub start: f64,
+    /// Sweep angle in radians. Negative for a counter-clockwise arc.
+    pub sweep: f64,
+}
+
+impl CenterArc {
+    /// Converts an arc from the endpoint parameterization.
+    ///
+    /// Returns `None` when the arc must be treated as a straight line,
+    /// i.e. when one of the radii is zero or the endpoints are equal.
+    pub fn from_endpoints(x1: f64, y1: f64, rx: f64, ry: f64, x_axis_rotation: f64,
+                          large_arc: bool, sweep: bool, x2: f64, y2: f64) -> Option<CenterArc> {
+        if rx.fuzzy_eq(&0.0) || ry.fuzzy_eq(&0.0) || (x1.fuzzy_eq(&x2) && y1.fuzzy_eq(&y2)) {
+            return None;
+        }
+
+        let mut rx = rx.abs();
+        let mut ry = ry.abs();
+
+        let phi = x_axis_rotation.to_radians();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+
+        let dx2 = (x1 - x2) / 2.0;
+        let dy2 = (y1 - y2) / 2.0;
+        let x1p =  cos_phi * dx2 + sin_phi * dy2;
+        let y1p = -sin_phi * dx2 + cos_phi * dy2;
+
+        // Scale up the radii if they are too small.
+        let lambda = (x1p * x1p) / (rx * rx) + (y1p * y1p) / (ry * ry);
+        if lambda > 1.0 {
+            let k = lambda.sqrt();
+            rx *= k;
+            ry *= k;
+        }
+
+        let rx2 = rx * rx;
+        let ry2 = ry * ry;
+        let num = rx2 * ry2 - rx2 * y1p * y1p - ry2 * x1p * x1p;
+        let den = rx2 * y1p * y1p + ry2 * x1p * x1p;
+        let mut coef = (num / den).max(0.0).sqrt();
+        if large_arc == sweep {
+            coef = -coef;
+        }
+
+        let cxp =  coef * rx * y1p / ry;
+        let cyp = -coef * ry * x1p / rx;
+
+        let cx = cos_phi * cxp - sin_phi * cyp + (x1 + x2) / 2.0;
+        let cy = sin_phi * cxp + cos_phi * cyp + (y1 + y2) / 2.0;
+
+        let start = ((y1p - cyp) / ry).atan2((x1p - cxp) / rx);
+        let end = ((-y1p - cyp) / ry).atan2((-x1p - cxp) / rx);
+
+        let mut delta = end - start;
+        if sweep && delta < 0.0 {
+            delta += 2.0 * f64::consts::PI;
+        } else if !sweep && delta > 0.0 {
+            delta -= 2.0 * f64::consts::PI;
+        }
+
+        Some(CenterArc {
+            cx: cx,
+            cy: cy,
+            rx: rx,
+            ry: ry,
+            phi: phi,
+            start: start,
+            sweep: delta,
+        })
+    }
+
+    /// Returns a point at the specified angle.
+    pub fn point_at(&self, angle: f64) -> (f64, f64) {
+        let (sin_phi, cos_phi) = self.phi.sin_cos();
+        let (sin_t, cos_t) = angle.sin_cos();
+        let x = self.rx * cos_t;
+        let y = self.ry * sin_t;
+        (self.cx + cos_phi * x - sin_phi * y, self.cy + sin_phi * x + cos_phi * y)
+    }
+
+    /// Checks that the specified angle lies inside the arc.
+    pub fn contains_angle(&self, angle: f64) -> bool {
+        let turn = 2.0 * f64::consts::PI;
+        let d = if self.sweep >= 0.0 { angle - self.start } else { self.start - angle };
+        let d = ((d % turn) + turn) % turn;
+        d <= self.sweep.abs()
+    }
+
+    /// Approximates the arc with cubic curves.
+    ///
+    /// Each curve covers no more than 90 degrees.
+    /// Returns a list of `(x1, y1, x2, y2, x, y)` tuples.
+    pub fn to_curves(self) -> Vec<(f64, f64, f64, f64, f64, f64)> {
+        let count = (self.sweep.abs() / f64::consts::FRAC_PI_2 - 1e-9).ceil().max(1.0) as usize;
+        let delta = self.sweep / count as f64;
+        let k = 4.0 / 3.0 * (delta / 4.0).tan();
+
+        let (sin_phi, cos_phi) = self.phi.sin_cos();
+        // Maps a point on the unit circle to the ellipse.
+        let map = |x: f64, y: f64| {
+            let x = x * self.rx;
+            let y = y * self.ry;
+            (self.cx + cos_phi * x - sin_phi * y, self.cy + sin_phi * x + cos_phi * y)
+        };
+
+        let mut curves = Vec::with_capacity(count);
+        for i in 0..count {
+            let a1 = self.start + delta * i as f64;
+            let a2 = a1 + delta;
+            let (sin1, cos1) = a1.sin_cos();
+            let (sin2, cos2) = a2.sin_cos();
+
+            let (x1, y1) = map(cos1 - k * sin1, sin1 + k * cos1);
+            let (x2, y2) = map(cos2 + k * sin2, sin2 - k * cos2);
+            let (x, y) = map(cos2, sin2);
+            curves.push((x1, y1, x2, y2, x, y));
+        }
+
+        curves
+    }
+}
@@ -0,0 +1,277 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::f64;
+
+use types::{
+    Rect,
+    Transform,
+};
+use super::{
+    Path,
+    SegmentData,
+};
+use super::arc::CenterArc;
+use super::iter_points;
+
+impl Path {
+    /// Calculates the path's bounding box.
+    ///
+    /// Curves and arcs are measured by their real extrema and not by control points.
+    ///
+    /// Returns `None` if the path doesn't contain any drawable segments.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::Rect;
+    /// use svgdom::path::Path;
+    ///
+    /// let path = Path::from_str("M 0 0 Q 5 10 10 0").unwrap();
+    /// assert_eq!(path.bbox(), Some(Rect::new(0.0, 0.0, 10.0, 5.0)));
+    /// ```
+    pub fn bbox(&self) -> Option<Rect> {
+        self.bbox_with_transform(&Transform::default())
+    }
+
+    /// Calculates the bounding box of the path after applying the transform.
+    ///
+    /// The result is an exact bounding box of the transformed path,
+    /// which is usually smaller than a transformed bounding box of the untransformed path.
+    ///
+    /// Returns `None` if the path doesn't contain any drawable segments.
+    pub fn bbox_with_transform(&self, ts: &Transform) -> Option<Rect> {
+        let mut bbox = BBox::new();
+
+        for ((prev_x, prev_y), _, seg) in iter_points(self) {
+            match seg {
+                SegmentData::MoveTo { .. } | SegmentData::ClosePath => continue,
+                _ => {}
+            }
+
+            bbox.add(ts.apply(prev_x, prev_y));
+
+            match seg {
+                SegmentData::LineTo { x, y } => {
+                    bbox.add(ts.apply(x, y));
+                }
+                SegmentData::CurveTo { x1, y1, x2, y2, x, y } => {
+                    let p0 = ts.apply(prev_x, prev_y);
+                    let p1 = ts.apply(x1, y1);
+                    let p2 = ts.apply(x2, y2);
+                    let p3 = ts.apply(x, y);
+
+                    for t in cubic_extrema(p0.0, p1.0, p2.0, p3.0).iter()
+                        .chain(cubic_extrema(p0.1, p1.1, p2.1, p3.1).iter()) {
+                        if let Some(t) = *t {
+                            bbox.add((cubic_at(p0.0, p1.0, p2.0, p3.0, t),
+                                      cubic_at(p0.1, p1.1, p2.1, p3.1, t)));
+                        }
+                    }
+
+                    bbox.add(p3);
+                }
+                SegmentData::Quadratic { x1, y1, x, y } => {
+                    let p0 = ts.apply(prev_x, prev_y);
+                    let p1 = ts.apply(x1, y1);
+                    let p2 = ts.apply(x, y);
+
+                    for t in &[quad_extremum(p0.0, p1.0, p2.0), quad_extremum(p0.1, p1.1, p2.1)] {
+                        if let Some(t) = *t {
+                            bbox.add((quad_at(p0.0, p1.0, p2.0, t), quad_at(p0.1, p1.1, p2.1, t)));
+                        }
+                    }
+
+                    bbox.add(p2);
+                }
+                SegmentData::EllipticalArc { rx, ry, x_axis_rotation, large_arc, sweep, x, y } => {
+                    let arc = CenterArc::from_endpoints(prev_x, prev_y, rx, ry, x_axis_rotation,
+                                                        large_arc, sweep, x, y);
+                    if let Some(arc) = arc {
+                        add_arc_extrema(&arc, ts, &mut bbox);
+                    }
+
+                    bbox.add(ts.apply(x, y));
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        bbox.to_rect()
+    }
+}
+
+struct BBox {
+    min_x: f64,
+    min_y: f64,
+    max_x: f64,
+    max_y: f64,
+}
+
+impl BBox {
+    fn new() -> BBox {
+        BBox {
+            min_x: f64::MAX,
+            min_y: f64::MAX,
+            max_x: f64::MIN,
+            max_y: f64::MIN,
+        }
+    }
+
+    fn add(&mut self, (x, y): (f64, f64)) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+
+    fn to_rect(&self) -> Option<Rect> {
+        if self.min_x > self.max_x {
+            return None;
+        }
+
+        Some(Rect::new(self.min_x, self.min_y, self.max_x - self.min_x, self.max_y - self.min_y))
+    }
+}
+
+fn cubic_at(p0: f64, p1: f64, p2: f64, p3: f64, t: f64) -> f64 {
+    let mt = 1.0 - t;
+    mt * mt * mt * p0 + 3.0 * mt * mt * t * p1 + 3.0 * mt * t * t * p2 + t * t * t * p3
+}
+
+fn quad_at(p0: f64, p1: f64, p2: f64, t: f64) -> f64 {
+    let mt = 1.0 - t;
+    mt * mt * p0 + 2.0 * mt * t * p1 + t * t * p2
+}
+
+// Returns parameters of the cubic curve extrema inside the (0, 1) range.
+fn cubic_extrema(p0: f64, p1: f64, p2: f64, p3: f64) -> [Option<f64>; 2] {
+    // Coefficients of the derivative divided by 3.
+    let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+    let b = 2.0 * (p0 - 2.0 * p1 + p2);
+    let c = p1 - p0;
+
+    let in_range = |t: f64| if t > 0.0 && t < 1.0 { Some(t) } else { None };
+
+    if a.abs() < 1e-12 {
+        if b.abs() < 1e-12 {
+            return [None, None];
+        }
+
+        return [in_range(-c / b), None];
+    }
+
+    let d = b * b - 4.0 * a * c;
+    if d < 0.0 {
+        return [None, None];
+    }
+
+    let d = d.sqrt();
+    [in_range((-b + d) / (2.0 * a)), in_range((-b - d) / (2.0 * a))]
+}
+
+// Returns a parameter of the quadratic curve extremum inside the (0, 1) range.
+fn quad_extremum(p0: f64, p1: f64, p2: f64) -> Option<f64> {
+    let d = p0 - 2.0 * p1 + p2;
+    if d.abs() < 1e-12 {
+        return None;
+    }
+
+    let t = (p0 - p1) / d;
+    if t > 0.0 && t < 1.0 { Some(t) } else { None }
+}
+
+fn add_arc_extrema(arc: &CenterArc, ts: &Transform, bbox: &mut BBox) {
+    // A transformed point of the arc is `C + U * cos(t) + V * sin(t)`,
+    // so each coordinate has extrema at `atan2(v, u)` and at the opposite angle.
+    let (sin_phi, cos_phi) = arc.phi.sin_cos();
+    let ux = arc.rx * (ts.a * cos_phi + ts.c * sin_phi);
+    let vx = arc.ry * (ts.c * cos_phi - ts.a * sin_phi);
+    let uy = arc.rx * (ts.b * cos_phi + ts.d * sin_phi);
+    let vy = arc.ry * (ts.d * cos_phi - ts.b * sin_phi);
+
+    let ax = vx.atan2(ux);
+    let ay = vy.atan2(uy);
+    for angle in &[ax, ax + f64::consts::PI, ay, ay + f64::consts::PI] {
+        if arc.contains_angle(*angle) {
+            let (x, y) = arc.point_at(*angle);
+            bbox.add(ts.apply(x, y));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use types::{
+        Rect,
+        Transform,
+    };
+    use types::path::Path;
+
+    macro_rules! test {
+        ($name:ident, $text:expr, $ts:expr, $rect:expr) => (
+            #[test]
+            fn $name() {
+                let path = Path::from_str($text).unwrap();
+                let bbox = path.bbox_with_transform(&$ts).unwrap();
+                assert_approx_eq!(bbox, $rect, 1e-9);
+            }
+        )
+    }
+
+    test!(line_1, "M 10 20 L 30 40", Transform::default(), Rect::new(10.0, 20.0, 20.0, 20.0));
+
+    test!(line_2, "M 10 20 h 10 v -30 z m 100 100", Transform::default(),
+          Rect::new(10.0, -10.0, 10.0, 30.0));
+
+    test!(curve_1, "M 0 0 C 0 10 10 10 10 0", Transform::default(),
+          Rect::new(0.0, 0.0, 10.0, 7.5));
+
+    test!(smooth_curve_1, "M 0 0 S 10 10 10 0", Transform::default(),
+          Rect::new(0.0, 0.0, 10.0, 40.0 / 9.0));
+
+    test!(quad_1, "M 0 0 Q 5 10 10 0 T 20 0", Transform::default(),
+          Rect::new(0.0, -5.0, 20.0, 10.0));
+
+    test!(arc_1, "M 0 0 A 10 10 0 0 1 20 0", Transform::default(),
+          Rect::new(0.0, -10.0, 20.0, 10.0));
+
+    test!(arc_2, "M 0 0 A 10 10 0 0 0 20 0", Transform::default(),
+          Rect::new(0.0, 0.0, 20.0, 10.0));
+
+    test!(arc_3, "M 0 0 A 20 10 0 1 1 0 20", Transform::default(),
+          Rect::new(0.0, 0.0, 20.0, 20.0));
+
+    // Radii are too small and must be scaled up.
+    test!(arc_4, "M 0 0 A 1 1 0 0 1 20 0", Transform::default(),
+          Rect::new(0.0, -10.0, 20.0, 10.0));
+
+    test!(arc_5, "M 0 0 A 10 0 0 0 1 20 10", Transform::default(),
+          Rect::new(0.0, 0.0, 20.0, 10.0));
+
+    test!(arc_6, "M 0 0 A 10 10 0 0 1 20 0", Transform::new(2.0, 0.0, 0.0, 1.0, 5.0, 0.0),
+          Rect::new(5.0, -10.0, 40.0, 10.0));
+
+    test!(transform_1, "M 0 0 L 10 0 L 10 20", Transform::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0),
+          Rect::new(-20.0, 0.0, 20.0, 10.0));
+
+    #[test]
+    fn transform_2() {
+        // A rotated circle has the same bounding box.
+        let path = Path::from_str("M 0 10 A 10 10 0 1 1 20 10 A 10 10 0 1 1 0 10").unwrap();
+        let mut ts = Transform::default();
+        ts.rotate(30.0);
+        let bbox = path.bbox_with_transform(&ts).unwrap();
+        let (cx, cy) = ts.apply(10.0, 10.0);
+        assert_approx_eq!(bbox, Rect::new(cx - 10.0, cy - 10.0, 20.0, 20.0), 1e-9);
+    }
+
+    #[test]
+    fn empty_1() {
+        assert_eq!(Path::new().bbox(), None);
+        assert_eq!(Path::from_str("M 10 20").unwrap().bbox(), None);
+    }
+}
@@ -0,0 +1,266 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::{
+    Command,
+    Path,
+    Segment,
+    SegmentData,
+};
+use super::arc::CenterArc;
+use super::{
+    iter_points,
+    quad_to_cubic,
+};
+
+impl Path {
+    /// Converts elliptical arcs into cubic curves.
+    ///
+    /// Arcs are converted using the [endpoint to center] parameterization
+    /// with out-of-range radii correction. Each curve covers no more than 90 degrees.
+    /// Arcs with a zero radius are converted into lines.
+    ///
+    /// Converted segments are always absolute. Other segments are not changed,
+    /// except smooth curves that follow an arc, which become explicit curves.
+    ///
+    /// [endpoint to center]: https://www.w3.org/TR/SVG/implnote.html#ArcConversionEndpointToCenter
+    pub fn arcs_to_curves(&mut self) {
+        replace_segments(self, |(px, py), _, data, new| {
+            if let SegmentData::EllipticalArc { rx, ry, x_axis_rotation, large_arc, sweep, x, y }
+                = *data {
+                match CenterArc::from_endpoints(px, py, rx, ry, x_axis_rotation,
+                                                large_arc, sweep, x, y) {
+                    Some(arc) => {
+                        let curves = arc.to_curves();
+                        let len = curves.len();
+                        for (i, &(x1, y1, x2, y2, cx, cy)) in curves.iter().enumerate() {
+                            let (cx, cy) = if i == len - 1 { (x, y) } else { (cx, cy) };
+                            new.push(Segment::new_curve_to(x1, y1, x2, y2, cx, cy));
+                        }
+                    }
+                    None => new.push(Segment::new_line_to(x, y)),
+                }
+
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Converts quadratic curves into cubic curves.
+    ///
+    /// Both `Quadratic` and `SmoothQuadratic` segments are converted.
+    ///
+    /// Converted segments are always absolute. Other segments are not changed,
+    /// except smooth curves that follow a quadratic curve, which become explicit curves.
+    pub fn quads_to_cubics(&mut self) {
+        replace_segments(self, |(px, py), _, data, new| {
+            if let SegmentData::Quadratic { x1, y1, x, y } = *data {
+                let ((cx1, cy1), (cx2, cy2)) = quad_to_cubic((px, py), (x1, y1), (x, y));
+                new.push(Segment::new_curve_to(cx1, cy1, cx2, cy2, x, y));
+
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Converts smooth curves into explicit one.
+    ///
+    /// `SmoothCurveTo` became `CurveTo` and `SmoothQuadratic` became `Quadratic`
+    /// with a reflected control point.
+    ///
+    /// Converted segments are always absolute. Other segments are not changed.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::path::Path;
+    ///
+    /// let mut path = Path::from_str("M 10 20 C 20 10 30 10 40 20 s 20 10 30 0").unwrap();
+    /// path.smooth_to_explicit();
+    /// assert_eq!(path.to_string(), "M 10 20 C 20 10 30 10 40 20 C 50 30 60 30 70 20");
+    /// ```
+    pub fn smooth_to_explicit(&mut self) {
+        replace_segments(self, |_, seg, data, new| {
+            match seg.cmd() {
+                Command::SmoothCurveTo | Command::SmoothQuadratic => {
+                    // Smooth segments are already resolved by the iterator.
+                    new.push(Segment { absolute: true, data: *data });
+                    true
+                }
+                _ => false,
+            }
+        });
+    }
+
+    /// Converts horizontal and vertical lines into explicit ones.
+    ///
+    /// `HorizontalLineTo` and `VerticalLineTo` became `LineTo`.
+    ///
+    /// Converted segments are always absolute. Other segments are not changed.
+    ///
+    /// Together with `arcs_to_curves`, `quads_to_cubics` and `smooth_to_explicit`
+    /// it converts a path into `MoveTo`, `LineTo`, `CurveTo` and `ClosePath` segments only.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::path::Path;
+    ///
+    /// let mut path = Path::from_str("M 10 20 H 30 v 10 h -20").unwrap();
+    /// path.lines_to_explicit();
+    /// assert_eq!(path.to_string(), "M 10 20 L 30 20 L 30 30 L 10 30");
+    /// ```
+    pub fn lines_to_explicit(&mut self) {
+        replace_segments(self, |_, seg, data, new| {
+            match seg.cmd() {
+                Command::HorizontalLineTo | Command::VerticalLineTo => {
+                    // Lines are already resolved by the iterator.
+                    new.push(Segment { absolute: true, data: *data });
+                    true
+                }
+                _ => false,
+            }
+        });
+    }
+}
+
+// Replaces segments using the `f` function.
+//
+// The function receives the current point, an original segment,
+// a resolved absolute segment and a list of new segments.
+// If it returns `false`, the original segment will be kept.
+//
+// Since converted segments have the same end point, following relative segments
+// are still valid. But smooth segments that are not preceded by a segment
+// of the same kind, have to be made explicit, because their previous segment
+// may become a curve and the reflection will be applied.
+fn replace_segments<F>(path: &mut Path, mut f: F)
+    where F: FnMut((f64, f64), &Segment, &SegmentData, &mut Vec<Segment>) -> bool
+{
+    let mut new = Vec::with_capacity(path.d.len());
+
+    let mut prev_cmd = Command::MoveTo;
+
+    for (seg, (prev, _, data)) in path.d.iter().zip(iter_points(path)) {
+        if !f(prev, seg, &data, &mut new) {
+            let is_explicit = match (seg.cmd(), prev_cmd) {
+                  (Command::SmoothCurveTo, Command::CurveTo)
+                | (Command::SmoothCurveTo, Command::SmoothCurveTo)
+                | (Command::SmoothQuadratic, Command::Quadratic)
+                | (Command::SmoothQuadratic, Command::SmoothQuadratic) => false,
+                  (Command::SmoothCurveTo, _)
+                | (Command::SmoothQuadratic, _) => true,
+                _ => false,
+            };
+
+            if is_explicit {
+                new.push(Segment { absolute: true, data: data });
+            } else {
+                new.push(*seg);
+            }
+        }
+
+        prev_cmd = seg.cmd();
+    }
+
+    path.d = new;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use types::path::{
+        Command,
+        Path,
+    };
+
+    macro_rules! test {
+        ($name:ident, $method:ident, $in_text:expr, $out_text:expr) => (
+            #[test]
+            fn $name() {
+                let mut path = Path::from_str($in_text).unwrap();
+                path.$method();
+                assert_eq_text!(path.to_string(), $out_text);
+            }
+        )
+    }
+
+    test!(smooth_1, smooth_to_explicit,
+          "M 10 20 s 10 10 20 0 s 10 10 20 0",
+          "M 10 20 C 10 20 20 30 30 20 C 40 10 40 30 50 20");
+
+    test!(smooth_2, smooth_to_explicit,
+          "M 10 20 q 10 -10 20 0 t 20 0 l 10 10",
+          "M 10 20 q 10 -10 20 0 Q 40 30 50 20 l 10 10");
+
+    test!(quads_1, quads_to_cubics,
+          "M 0 0 Q 30 30 60 0 l 10 10",
+          "M 0 0 C 20 20 40 20 60 0 l 10 10");
+
+    test!(quads_2, quads_to_cubics,
+          "M 0 0 Q 30 30 60 0 T 120 0",
+          "M 0 0 C 20 20 40 20 60 0 C 80 -20 100 -20 120 0");
+
+    // A smooth curve after a quadratic curve doesn't have a reflected control point.
+    test!(quads_3, quads_to_cubics,
+          "M 0 0 Q 30 30 60 0 S 90 30 120 0",
+          "M 0 0 C 20 20 40 20 60 0 C 60 0 90 30 120 0");
+
+    test!(quads_4, quads_to_cubics,
+          "M 0 0 C 10 10 20 10 30 0 S 50 -10 60 0",
+          "M 0 0 C 10 10 20 10 30 0 S 50 -10 60 0");
+
+    test!(lines_1, lines_to_explicit,
+          "M 10 20 H 30 v 10 h -20 V 20 l 5 5",
+          "M 10 20 L 30 20 L 30 30 L 10 30 L 10 20 l 5 5");
+
+    test!(arcs_1, arcs_to_curves,
+          "M 0 0 A 0 10 0 0 1 20 0 l 10 10",
+          "M 0 0 L 20 0 l 10 10");
+
+    test!(arcs_2, arcs_to_curves,
+          "M 0 0 a 10 10 0 0 1 20 0 s 10 10 20 0",
+          "M 0 0 C 0 -5.52284749831 4.47715250169 -10 10 -10 \
+           C 15.52284749831 -10 20 -5.52284749831 20 0 C 20 0 30 10 40 0");
+
+    #[test]
+    fn arcs_3() {
+        let text = "M 10 20 A 30 10 45 1 0 40 30 a 5 5 0 0 1 -20 0 Z";
+        let mut path = Path::from_str(text).unwrap();
+        let bbox = path.bbox().unwrap();
+        path.arcs_to_curves();
+
+        assert!(path.d.iter().all(|seg| {
+            seg.cmd() == Command::MoveTo || seg.cmd() == Command::CurveTo
+                || seg.cmd() == Command::ClosePath
+        }));
+
+        assert_eq!(path.d[path.d.len() - 2].x(), Some(20.0));
+        assert_eq!(path.d[path.d.len() - 2].y(), Some(30.0));
+
+        // The approximation error is very small.
+        assert_approx_eq!(path.bbox().unwrap(), bbox, 0.01);
+    }
+
+    #[test]
+    fn normalize_1() {
+        let text = "M 10 20 h 10 V 40 A 10 10 0 0 1 40 40 q 10 10 20 0 t 20 0 \
+                    s 10 10 20 0 z m 5 5 H 0 Z";
+        let mut path = Path::from_str(text).unwrap();
+        path.arcs_to_curves();
+        path.quads_to_cubics();
+        path.smooth_to_explicit();
+        path.lines_to_explicit();
+
+        assert!(path.d.iter().all(|seg| {
+            seg.cmd() == Command::MoveTo || seg.cmd() == Command::LineTo
+                || seg.cmd() == Command::CurveTo || seg.cmd() == Command::ClosePath
+        }), "{}", path);
+    }
+}
@@ -0,0 +1,268 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::f64;
+
+use types::Points;
+use super::{
+    Path,
+    SegmentData,
+};
+use super::arc::CenterArc;
+use super::{
+    iter_points,
+    quad_to_cubic,
+};
+
+// Limits the number of lines per segment, which prevents an endless subdivision
+// of degenerate curves or when the tolerance is too small.
+const MAX_DEPTH: u32 = 16;
+
+// The smallest allowed tolerance.
+const MIN_TOLERANCE: f64 = 1e-6;
+
+impl Path {
+    /// Converts the path into polylines.
+    ///
+    /// Each subpath is converted into a separate polyline. A closed subpath
+    /// ends with its start point. Subpaths without drawable segments are skipped.
+    ///
+    /// Curves and arcs are approximated by lines using an adaptive subdivision,
+    /// so the maximum distance between a curve and its polyline is not greater
+    /// than `tolerance`. The number of lines per segment is limited to 65536,
+    /// so the guarantee cannot be fulfilled for a very small `tolerance`.
+    ///
+    /// A `tolerance` that is smaller than `1e-6`, including a negative one and NaN,
+    /// is replaced with `1e-6`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::path::Path;
+    ///
+    /// let path = Path::from_str("M 10 20 L 30 40 Z M 100 100 q 10 10 20 0").unwrap();
+    /// let lines = path.flatten(0.1);
+    /// assert_eq!(lines.len(), 2);
+    /// assert_eq!(lines[0].to_string(), "10 20 30 40 10 20");
+    /// ```
+    pub fn flatten(&self, tolerance: f64) -> Vec<Points> {
+        // NaN is replaced too, since any comparison with it is `false`.
+        let tolerance = if tolerance > MIN_TOLERANCE { tolerance } else { MIN_TOLERANCE };
+
+        let mut polylines = Vec::new();
+        let mut points = Vec::new();
+
+        for (prev, start, seg) in iter_points(self) {
+            match seg {
+                SegmentData::MoveTo { .. } => {
+                    finish_polyline(&mut points, &mut polylines);
+                    continue;
+                }
+                SegmentData::ClosePath => {
+                    if !points.is_empty() {
+                        points.push(start);
+                    }
+
+                    finish_polyline(&mut points, &mut polylines);
+                    continue;
+                }
+                _ => {}
+            }
+
+            if points.is_empty() {
+                points.push(prev);
+            }
+
+            match seg {
+                SegmentData::LineTo { x, y } => {
+                    points.push((x, y));
+                }
+                SegmentData::CurveTo { x1, y1, x2, y2, x, y } => {
+                    flatten_cubic([prev, (x1, y1), (x2, y2), (x, y)], tolerance, 0, &mut points);
+                }
+                SegmentData::Quadratic { x1, y1, x, y } => {
+                    let (c1, c2) = quad_to_cubic(prev, (x1, y1), (x, y));
+                    flatten_cubic([prev, c1, c2, (x, y)], tolerance, 0, &mut points);
+                }
+                SegmentData::EllipticalArc { rx, ry, x_axis_rotation, large_arc, sweep, x, y } => {
+                    let arc = CenterArc::from_endpoints(prev.0, prev.1, rx, ry, x_axis_rotation,
+                                                        large_arc, sweep, x, y);
+                    if let Some(arc) = arc {
+                        flatten_arc(&arc, tolerance, &mut points);
+                    }
+
+                    points.push((x, y));
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        finish_polyline(&mut points, &mut polylines);
+
+        polylines
+    }
+}
+
+fn finish_polyline(points: &mut Vec<(f64, f64)>, polylines: &mut Vec<Points>) {
+    if points.len() > 1 {
+        polylines.push(Points(points.clone()));
+    }
+
+    points.clear();
+}
+
+// Appends a flattened cubic curve to the points, except the start point.
+fn flatten_cubic(p: [(f64, f64); 4], tolerance: f64, depth: u32, points: &mut Vec<(f64, f64)>) {
+    // The curve lies inside the convex hull of its control points,
+    // so the distance between the curve and the chord can't be greater
+    // than the distance from the control points to the chord.
+    let d = dist_to_segment(p[1], p[0], p[3]).max(dist_to_segment(p[2], p[0], p[3]));
+    if d <= tolerance || depth >= MAX_DEPTH {
+        points.push(p[3]);
+        return;
+    }
+
+    // Split the curve at the middle using de Casteljau's algorithm.
+    let mid = |a: (f64, f64), b: (f64, f64)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+    let p01 = mid(p[0], p[1]);
+    let p12 = mid(p[1], p[2]);
+    let p23 = mid(p[2], p[3]);
+    let p012 = mid(p01, p12);
+    let p123 = mid(p12, p23);
+    let p0123 = mid(p012, p123);
+
+    flatten_cubic([p[0], p01, p012, p0123], tolerance, depth + 1, points);
+    flatten_cubic([p0123, p123, p23, p[3]], tolerance, depth + 1, points);
+}
+
+// Appends a flattened arc to the points, except the start and end points.
+fn flatten_arc(arc: &CenterArc, tolerance: f64, points: &mut Vec<(f64, f64)>) {
+    // The distance between a circle arc and its chord is `r * (1 - cos(angle / 2))`.
+    // An ellipse is a scaled circle, so the largest radius is used.
+    let r = arc.rx.max(arc.ry);
+    let step = if tolerance >= r {
+        f64::consts::PI
+    } else {
+        2.0 * (1.0 - tolerance / r).acos()
+    };
+
+    let max_count = (1u32 << MAX_DEPTH) as f64;
+    let count = (arc.sweep.abs() / step).ceil().max(1.0).min(max_count) as u32;
+    let delta = arc.sweep / count as f64;
+    for i in 1..count {
+        points.push(arc.point_at(arc.start + delta * i as f64));
+    }
+}
+
+fn dist_to_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let dx = b.0 - a.0;
+    let dy = b.1 - a.1;
+    let len2 = dx * dx + dy * dy;
+
+    let (x, y) = if len2 == 0.0 {
+        a
+    } else {
+        let t = (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len2).max(0.0).min(1.0);
+        (a.0 + t * dx, a.1 + t * dy)
+    };
+
+    ((p.0 - x) * (p.0 - x) + (p.1 - y) * (p.1 - y)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64;
+    use std::str::FromStr;
+
+    use types::path::Path;
+    use super::dist_to_segment;
+
+    // Returns the distance from the point to the polyline.
+    fn dist_to_polyline(p: (f64, f64), points: &[(f64, f64)]) -> f64 {
+        points.windows(2).map(|w| dist_to_segment(p, w[0], w[1])).fold(f64::MAX, f64::min)
+    }
+
+    macro_rules! test {
+        ($name:ident, $text:expr, $points:expr) => (
+            #[test]
+            fn $name() {
+                let path = Path::from_str($text).unwrap();
+                let lines: Vec<String> = path.flatten(0.1).iter().map(|p| p.to_string()).collect();
+                let points: &[&str] = &$points;
+                assert_eq!(lines, points);
+            }
+        )
+    }
+
+    test!(lines_1, "M 10 20 h 10 v 10 Z", ["10 20 20 20 20 30 10 20"]);
+
+    test!(lines_2, "M 10 20 L 20 20 Z l 10 10 M 100 100 M 50 50 L 60 60",
+          ["10 20 20 20 10 20", "10 20 20 30", "50 50 60 60"]);
+
+    // Straight curves are not subdivided.
+    test!(curve_1, "M 0 0 C 10 0 20 0 30 0 Q 40 0 50 0", ["0 0 30 0 50 0"]);
+
+    test!(arc_1, "M 0 0 A 0 10 0 0 1 20 0 A 10 10 0 0 1 20 0", ["0 0 20 0 20 0"]);
+
+    #[test]
+    fn tolerance_1() {
+        let path = Path::from_str("M 10 20 L 30 40 A 10 10 0 0 1 50 40 Q 60 50 70 40").unwrap();
+        let lines = path.flatten(1e-6);
+        assert!(lines[0].len() > 4);
+        assert_eq!(path.flatten(0.0), lines);
+        assert_eq!(path.flatten(-1.0), lines);
+        assert_eq!(path.flatten(f64::NAN), lines);
+    }
+
+    #[test]
+    fn curve_2() {
+        let path = Path::from_str("M 10 20 C 0 100 100 -50 200 50 Q 250 100 300 50").unwrap();
+        for &tolerance in &[1.0, 0.1, 0.01] {
+            let lines = path.flatten(tolerance);
+            assert_eq!(lines.len(), 1);
+            assert_eq!(lines[0][0], (10.0, 20.0));
+            assert_eq!(lines[0][lines[0].len() - 1], (300.0, 50.0));
+
+            for i in 0..101 {
+                let t = i as f64 / 100.0;
+                let mt = 1.0 - t;
+                let x = mt * mt * mt * 10.0 + 3.0 * mt * mt * t * 0.0
+                        + 3.0 * mt * t * t * 100.0 + t * t * t * 200.0;
+                let y = mt * mt * mt * 20.0 + 3.0 * mt * mt * t * 100.0
+                        + 3.0 * mt * t * t * -50.0 + t * t * t * 50.0;
+                assert!(dist_to_polyline((x, y), &lines[0]) <= tolerance);
+
+                let x = mt * mt * 200.0 + 2.0 * mt * t * 250.0 + t * t * 300.0;
+                let y = mt * mt * 50.0 + 2.0 * mt * t * 100.0 + t * t * 50.0;
+                assert!(dist_to_polyline((x, y), &lines[0]) <= tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn arc_2() {
+        // A rotated ellipse with the center at 50,50.
+        let path = Path::from_str("M 50 10 A 40 20 90 1 1 50 90 A 40 20 90 1 1 50 10 Z").unwrap();
+        for &tolerance in &[1.0, 0.1, 0.01] {
+            let lines = path.flatten(tolerance);
+            assert_eq!(lines.len(), 1);
+
+            let points = &lines[0];
+            assert_eq!(points[0], (50.0, 10.0));
+            assert_eq!(points[points.len() - 1], (50.0, 10.0));
+
+            // All points are on the ellipse.
+            for &(x, y) in points.iter() {
+                let v = ((x - 50.0) / 20.0).powi(2) + ((y - 50.0) / 40.0).powi(2);
+                assert!((v - 1.0).abs() < 1e-9);
+            }
+
+            for i in 0..360 {
+                let a = (i as f64).to_radians();
+                let p = (50.0 + 20.0 * a.cos(), 50.0 + 40.0 * a.sin());
+                assert!(dist_to_polyline(p, points) <= tolerance);
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::slice;
+
+use super::{
+    Path,
+    Segment,
+    SegmentData,
+};
+
+/// An iterator over fully resolved absolute path segments.
+///
+/// Relative segments are converted into absolute one and implicit commands are expanded:
+///
+/// - `HorizontalLineTo` and `VerticalLineTo` became `LineTo`.
+/// - `SmoothCurveTo` became `CurveTo` with a reflected first control point.
+/// - `SmoothQuadratic` became `Quadratic` with a reflected control point.
+///
+/// So only `MoveTo`, `LineTo`, `CurveTo`, `Quadratic`, `EllipticalArc`
+/// and `ClosePath` will be returned.
+///
+/// Unlike `Path::conv_to_absolute`, the path itself is not modified.
+pub struct AbsoluteSegments<'a> {
+    iter: slice::Iter<'a, Segment>,
+    // Current point.
+    prev_x: f64,
+    prev_y: f64,
+    // Start point of the current subpath.
+    start_x: f64,
+    start_y: f64,
+    // Last control point of the previous cubic or quadratic curve.
+    prev_cubic: Option<(f64, f64)>,
+    prev_quad: Option<(f64, f64)>,
+}
+
+impl<'a> Iterator for AbsoluteSegments<'a> {
+    type Item = SegmentData;
+
+    fn next(&mut self) -> Option<SegmentData> {
+        let seg = match self.iter.next() {
+            Some(seg) => seg,
+            None => return None,
+        };
+
+        let (dx, dy) = if seg.is_relative() { (self.prev_x, self.prev_y) } else { (0.0, 0.0) };
+
+        let mut prev_cubic = None;
+        let mut prev_quad = None;
+
+        let data = match *seg.data() {
+            SegmentData::MoveTo { x, y } => {
+                let (x, y) = (x + dx, y + dy);
+                self.start_x = x;
+                self.start_y = y;
+                SegmentData::MoveTo { x: x, y: y }
+            }
+            SegmentData::LineTo { x, y } => {
+                SegmentData::LineTo { x: x + dx, y: y + dy }
+            }
+            SegmentData::HorizontalLineTo { x } => {
+                SegmentData::LineTo { x: x + dx, y: self.prev_y }
+            }
+            SegmentData::VerticalLineTo { y } => {
+                SegmentData::LineTo { x: self.prev_x, y: y + dy }
+            }
+            SegmentData::CurveTo { x1, y1, x2, y2, x, y } => {
+                prev_cubic = Some((x2 + dx, y2 + dy));
+                SegmentData::CurveTo {
+                    x1: x1 + dx,
+                    y1: y1 + dy,
+                    x2: x2 + dx,
+                    y2: y2 + dy,
+                    x: x + dx,
+                    y: y + dy,
+                }
+            }
+            SegmentData::SmoothCurveTo { x2, y2, x, y } => {
+                let (x1, y1) = self.reflect(self.prev_cubic);
+                prev_cubic = Some((x2 + dx, y2 + dy));
+                SegmentData::CurveTo {
+                    x1: x1,
+                    y1: y1,
+                    x2: x2 + dx,
+                    y2: y2 + dy,
+                    x: x + dx,
+                    y: y + dy,
+                }
+            }
+            SegmentData::Quadratic { x1, y1, x, y } => {
+                prev_quad = Some((x1 + dx, y1 + dy));
+                SegmentData::Quadratic {
+                    x1: x1 + dx,
+                    y1: y1 + dy,
+                    x: x + dx,
+                    y: y + dy,
+                }
+            }
+            SegmentData::SmoothQuadratic { x, y } => {
+                let (x1, y1) = self.reflect(self.prev_quad);
+                prev_quad = Some((x1, y1));
+                SegmentData::Quadratic {
+                    x1: x1,
+                    y1: y1,
+                    x: x + dx,
+                    y: y + dy,
+                }
+            }
+            SegmentData::EllipticalArc { rx, ry, x_axis_rotation, large_arc, sweep, x, y } => {
+                SegmentData::EllipticalArc {
+                    rx: rx,
+                    ry: ry,
+                    x_axis_rotation: x_axis_rotation,
+                    large_arc: large_arc,
+                    sweep: sweep,
+                    x: x + dx,
+                    y: y + dy,
+                }
+            }
+            SegmentData::ClosePath => SegmentData::ClosePath,
+        };
+
+        match data {
+            SegmentData::ClosePath => {
+                self.prev_x = self.start_x;
+                self.prev_y = self.start_y;
+            }
+              SegmentData::MoveTo { x, y }
+            | SegmentData::LineTo { x, y }
+            | SegmentData::CurveTo { x, y, .. }
+            | SegmentData::Quadratic { x, y, .. }
+            | SegmentData::EllipticalArc { x, y, .. } => {
+                self.prev_x = x;
+                self.prev_y = y;
+            }
+            _ => unreachable!(),
+        }
+
+        self.prev_cubic = prev_cubic;
+        self.prev_quad = prev_quad;
+
+        Some(data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> AbsoluteSegments<'a> {
+    // Reflects a control point about the current point.
+    //
+    // If there is no previous control point, the current point is used.
+    fn reflect(&self, p: Option<(f64, f64)>) -> (f64, f64) {
+        match p {
+            Some((x, y)) => (self.prev_x * 2.0 - x, self.prev_y * 2.0 - y),
+            None => (self.prev_x, self.prev_y),
+        }
+    }
+}
+
+impl Path {
+    /// Returns an iterator over fully resolved absolute segments.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::path::{Path, SegmentData};
+    ///
+    /// let path = Path::from_str("m 10 20 h 10 s 10 10 20 0").unwrap();
+    /// let segments: Vec<_> = path.iter_absolute().collect();
+    /// assert_eq!(segments, vec![
+    ///     SegmentData::MoveTo { x: 10.0, y: 20.0 },
+    ///     SegmentData::LineTo { x: 20.0, y: 20.0 },
+    ///     SegmentData::CurveTo { x1: 20.0, y1: 20.0, x2: 30.0, y2: 30.0, x: 40.0, y: 20.0 },
+    /// ]);
+    /// ```
+    pub fn iter_absolute<'a>(&'a self) -> AbsoluteSegments<'a> {
+        AbsoluteSegments {
+            iter: self.d.iter(),
+            prev_x: 0.0,
+            prev_y: 0.0,
+            start_x: 0.0,
+            start_y: 0.0,
+            prev_cubic: None,
+            prev_quad: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use types::path::{
+        Path,
+        Segment,
+    };
+
+    macro_rules! test {
+        ($name:ident, $in_text:expr, $out_text:expr) => (
+            #[test]
+            fn $name() {
+                let path = Path::from_str($in_text).unwrap();
+                let mut new_path = Path::new();
+                for data in path.iter_absolute() {
+                    new_path.d.push(Segment { absolute: true, data: data });
+                }
+                assert_eq_text!(new_path.to_string(), $out_text);
+            }
+        )
+    }
+
+    test!(line_to,
+          "m 10 20 l 20 20",
+          "M 10 20 L 30 40");
+
+    test!(hline_vline,
+          "m 10 20 v 10 h 10 V 50 H 0",
+          "M 10 20 L 10 30 L 20 30 L 20 50 L 0 50");
+
+    test!(close_path,
+          "M 10 20 L 30 40 Z l 10 10 z m 10 0 l 10 10",
+          "M 10 20 L 30 40 Z L 20 30 Z M 20 20 L 30 30");
+
+    test!(smooth_curve_1,
+          "M 10 20 C 20 10 30 10 40 20 S 60 30 70 20",
+          "M 10 20 C 20 10 30 10 40 20 C 50 30 60 30 70 20");
+
+    test!(smooth_curve_2,
+          "M 10 20 s 10 10 20 0 s 10 10 20 0",
+          "M 10 20 C 10 20 20 30 30 20 C 40 10 40 30 50 20");
+
+    // Reflection is only applied after a cubic curve.
+    test!(smooth_curve_3,
+          "M 10 20 Q 20 10 30 20 S 50 30 60 20",
+          "M 10 20 Q 20 10 30 20 C 30 20 50 30 60 20");
+
+    test!(smooth_quad_1,
+          "M 10 20 q 10 -10 20 0 t 20 0 t 20 0",
+          "M 10 20 Q 20 10 30 20 Q 40 30 50 20 Q 60 10 70 20");
+
+    test!(smooth_quad_2,
+          "M 10 20 T 30 20",
+          "M 10 20 Q 10 20 30 20");
+
+    test!(arc,
+          "M 30 150 a 40 40 0 0 1 65 50",
+          "M 30 150 A 40 40 0 0 1 95 200");
+
+    #[test]
+    fn not_modified() {
+        let path = Path::from_str("m 10 20 l 20 20").unwrap();
+        assert_eq!(path.iter_absolute().count(), 2);
+        assert_eq_text!(path.to_string(), "m 10 20 l 20 20");
+    }
+}
@@ -0,0 +1,521 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::f64;
+
+use super::{
+    Path,
+    Segment,
+    SegmentData,
+};
+use super::arc::CenterArc;
+use super::{
+    iter_points,
+    Point,
+};
+
+impl Path {
+    /// Calculates the path length.
+    ///
+    /// Includes lines created by `ClosePath` segments.
+    /// Curves and arcs are measured using a numerical integration.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::path::Path;
+    ///
+    /// let path = Path::from_str("M 10 20 l 30 40 h -30 z").unwrap();
+    /// assert_eq!(path.length(), 120.0);
+    /// ```
+    pub fn length(&self) -> f64 {
+        pieces(self).iter().map(|p| p.curve.length()).sum()
+    }
+
+    /// Returns a point at the specified distance along the path.
+    ///
+    /// The result is `(x, y, angle)`, where `angle` is the tangent direction in degrees.
+    ///
+    /// The distance is clamped to the path length.
+    ///
+    /// Returns `None` if the path doesn't contain any drawable segments.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::path::Path;
+    ///
+    /// let path = Path::from_str("M 10 20 h 10 v 10").unwrap();
+    /// assert_eq!(path.point_at_length(15.0), Some((20.0, 25.0, 90.0)));
+    /// ```
+    pub fn point_at_length(&self, length: f64) -> Option<(f64, f64, f64)> {
+        let pieces = pieces(self);
+        let (idx, t) = match find_piece(&pieces, length.max(0.0)) {
+            Some(v) => v,
+            None => {
+                // Use the end of the path.
+                if pieces.is_empty() {
+                    return None;
+                }
+
+                (pieces.len() - 1, 1.0)
+            }
+        };
+
+        let curve = &pieces[idx].curve;
+        let (x, y) = curve.point_at(t);
+        let (dx, dy) = curve.tangent_at(t);
+        Some((x, y, dy.atan2(dx).to_degrees()))
+    }
+
+    /// Splits the path at the specified distance.
+    ///
+    /// Segments before the split point are preserved as is and the split segment
+    /// is replaced by its absolute first part. The second path starts with
+    /// an absolute `MoveTo` and contains only absolute segments.
+    /// Each `ClosePath` of the split subpath becomes `LineTo` to its start point.
+    ///
+    /// If the distance is not positive, the first path will be empty.
+    /// If the distance is not less than the path length, the second path will be empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::path::Path;
+    ///
+    /// let path = Path::from_str("M 10 20 l 30 40 h -30 z").unwrap();
+    /// let (p1, p2) = path.split_at_length(25.0);
+    /// assert_eq!(p1.to_string(), "M 10 20 L 25 40");
+    /// assert_eq!(p2.to_string(), "M 25 40 L 40 60 L 10 60 L 10 20");
+    /// ```
+    pub fn split_at_length(&self, length: f64) -> (Path, Path) {
+        let pieces = pieces(self);
+
+        if length <= 0.0 {
+            return (Path::new(), self.clone());
+        }
+
+        if length >= pieces.iter().map(|p| p.curve.length()).sum() {
+            return (self.clone(), Path::new());
+        }
+
+        let (idx, t) = match find_piece(&pieces, length) {
+            Some(v) => v,
+            None => return (self.clone(), Path::new()),
+        };
+
+        let piece = &pieces[idx];
+        let (first, second) = piece.curve.split(t);
+        let (x, y) = piece.curve.point_at(t);
+
+        let mut p1 = Path::with_capacity(piece.index + 1);
+        p1.d.extend_from_slice(&self.d[..piece.index]);
+        p1.d.push(Segment { absolute: true, data: first });
+
+        let mut p2 = Path::with_capacity(self.d.len() - piece.index + 1);
+        p2.d.push(Segment::new_move_to(x, y));
+        p2.d.push(Segment { absolute: true, data: second });
+
+        let mut is_split_subpath = true;
+        for data in self.iter_absolute().skip(piece.index + 1) {
+            match data {
+                SegmentData::MoveTo { .. } => {
+                    is_split_subpath = false;
+                }
+                SegmentData::ClosePath if is_split_subpath => {
+                    // The second path has a different subpath start.
+                    let (sx, sy) = piece.start;
+                    p2.d.push(Segment::new_line_to(sx, sy));
+                    continue;
+                }
+                _ => {}
+            }
+
+            p2.d.push(Segment { absolute: true, data: data });
+        }
+
+        (p1, p2)
+    }
+}
+
+enum Curve {
+    Line([Point; 2]),
+    Quad([Point; 3]),
+    Cubic([Point; 4]),
+    Arc(CenterArc),
+}
+
+struct Piece {
+    // Index of the original segment.
+    index: usize,
+    // Start point of the subpath.
+    start: Point,
+    curve: Curve,
+}
+
+// Converts the path into a list of drawable pieces.
+fn pieces(path: &Path) -> Vec<Piece> {
+    let mut pieces = Vec::with_capacity(path.d.len());
+
+    for (index, (prev, start, seg)) in iter_points(path).enumerate() {
+        let curve = match seg {
+            SegmentData::MoveTo { .. } => continue,
+            SegmentData::ClosePath => Curve::Line([prev, start]),
+            SegmentData::LineTo { x, y } => Curve::Line([prev, (x, y)]),
+            SegmentData::CurveTo { x1, y1, x2, y2, x, y } => {
+                Curve::Cubic([prev, (x1, y1), (x2, y2), (x, y)])
+            }
+            SegmentData::Quadratic { x1, y1, x, y } => {
+                Curve::Quad([prev, (x1, y1), (x, y)])
+            }
+            SegmentData::EllipticalArc { rx, ry, x_axis_rotation, large_arc, sweep, x, y } => {
+                match CenterArc::from_endpoints(prev.0, prev.1, rx, ry, x_axis_rotation,
+                                                large_arc, sweep, x, y) {
+                    Some(arc) => Curve::Arc(arc),
+                    None => Curve::Line([prev, (x, y)]),
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        pieces.push(Piece {
+            index: index,
+            start: start,
+            curve: curve,
+        });
+    }
+
+    pieces
+}
+
+// Returns an index of the piece and the curve parameter at the specified distance.
+//
+// Returns `None` if the distance is greater than the path length.
+fn find_piece(pieces: &[Piece], length: f64) -> Option<(usize, f64)> {
+    let mut offset = 0.0;
+    for (i, piece) in pieces.iter().enumerate() {
+        let len = piece.curve.length();
+        if len > 0.0 && offset + len >= length {
+            return Some((i, piece.curve.t_at_length(length - offset, len)));
+        }
+
+        offset += len;
+    }
+
+    None
+}
+
+impl Curve {
+    fn point_at(&self, t: f64) -> Point {
+        let mt = 1.0 - t;
+        match *self {
+            Curve::Line(p) => {
+                (p[0].0 * mt + p[1].0 * t, p[0].1 * mt + p[1].1 * t)
+            }
+            Curve::Quad(p) => {
+                let a = mt * mt;
+                let b = 2.0 * mt * t;
+                let c = t * t;
+                (a * p[0].0 + b * p[1].0 + c * p[2].0, a * p[0].1 + b * p[1].1 + c * p[2].1)
+            }
+            Curve::Cubic(p) => {
+                let a = mt * mt * mt;
+                let b = 3.0 * mt * mt * t;
+                let c = 3.0 * mt * t * t;
+                let d = t * t * t;
+                (a * p[0].0 + b * p[1].0 + c * p[2].0 + d * p[3].0,
+                 a * p[0].1 + b * p[1].1 + c * p[2].1 + d * p[3].1)
+            }
+            Curve::Arc(ref arc) => arc.point_at(arc.start + arc.sweep * t),
+        }
+    }
+
+    // Returns a derivative at the specified parameter.
+    fn derivative_at(&self, t: f64) -> Point {
+        let mt = 1.0 - t;
+        match *self {
+            Curve::Line(p) => (p[1].0 - p[0].0, p[1].1 - p[0].1),
+            Curve::Quad(p) => {
+                (2.0 * (mt * (p[1].0 - p[0].0) + t * (p[2].0 - p[1].0)),
+                 2.0 * (mt * (p[1].1 - p[0].1) + t * (p[2].1 - p[1].1)))
+            }
+            Curve::Cubic(p) => {
+                let a = 3.0 * mt * mt;
+                let b = 6.0 * mt * t;
+                let c = 3.0 * t * t;
+                (a * (p[1].0 - p[0].0) + b * (p[2].0 - p[1].0) + c * (p[3].0 - p[2].0),
+                 a * (p[1].1 - p[0].1) + b * (p[2].1 - p[1].1) + c * (p[3].1 - p[2].1))
+            }
+            Curve::Arc(ref arc) => {
+                let angle = arc.start + arc.sweep * t;
+                let (sin_phi, cos_phi) = arc.phi.sin_cos();
+                let (sin_t, cos_t) = angle.sin_cos();
+                let dx = -arc.rx * sin_t * arc.sweep;
+                let dy = arc.ry * cos_t * arc.sweep;
+                (cos_phi * dx - sin_phi * dy, sin_phi * dx + cos_phi * dy)
+            }
+        }
+    }
+
+    // Returns a tangent direction at the specified parameter.
+    fn tangent_at(&self, t: f64) -> Point {
+        let (dx, dy) = self.derivative_at(t);
+        if dx.abs() > 1e-12 || dy.abs() > 1e-12 {
+            return (dx, dy);
+        }
+
+        // The derivative is zero when control points are equal to end points,
+        // so the direction is calculated using neighbour points.
+        let (x1, y1) = self.point_at((t - 1e-6).max(0.0));
+        let (x2, y2) = self.point_at((t + 1e-6).min(1.0));
+        (x2 - x1, y2 - y1)
+    }
+
+    fn length(&self) -> f64 {
+        self.length_to(1.0)
+    }
+
+    // Returns the length of the curve part in the 0..t range.
+    fn length_to(&self, t: f64) -> f64 {
+        match *self {
+            Curve::Line(p) => {
+                let dx = p[1].0 - p[0].0;
+                let dy = p[1].1 - p[0].1;
+                (dx * dx + dy * dy).sqrt() * t
+            }
+            _ => {
+                // Composite Gauss-Legendre quadrature.
+                const STEPS: usize = 16;
+                const NODES: [(f64, f64); 5] = [
+                    (0.0, 0.5688888888888889),
+                    (-0.5384693101056831, 0.4786286704993665),
+                    (0.5384693101056831, 0.4786286704993665),
+                    (-0.906179845938664, 0.2369268850561891),
+                    (0.906179845938664, 0.2369268850561891),
+                ];
+
+                let h = t / STEPS as f64;
+                let mut sum = 0.0;
+                for i in 0..STEPS {
+                    let mid = h * (i as f64 + 0.5);
+                    for &(x, w) in &NODES {
+                        let (dx, dy) = self.derivative_at(mid + x * h / 2.0);
+                        sum += w * (dx * dx + dy * dy).sqrt();
+                    }
+                }
+
+                sum * h / 2.0
+            }
+        }
+    }
+
+    // Returns the curve parameter at the specified distance.
+    fn t_at_length(&self, length: f64, total: f64) -> f64 {
+        if let Curve::Line(_) = *self {
+            return length / total;
+        }
+
+        // The length is a monotonic function of the parameter, so a bisection is used.
+        let mut lo = 0.0;
+        let mut hi = 1.0;
+        for _ in 0..50 {
+            let mid = (lo + hi) / 2.0;
+            if self.length_to(mid) < length {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        (lo + hi) / 2.0
+    }
+
+    // Splits the curve into two absolute segments.
+    fn split(&self, t: f64) -> (SegmentData, SegmentData) {
+        let lerp = |a: Point, b: Point| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+
+        match *self {
+            Curve::Line(p) => {
+                let (x, y) = lerp(p[0], p[1]);
+                (SegmentData::LineTo { x: x, y: y },
+                 SegmentData::LineTo { x: p[1].0, y: p[1].1 })
+            }
+            Curve::Quad(p) => {
+                let p01 = lerp(p[0], p[1]);
+                let p12 = lerp(p[1], p[2]);
+                let p012 = lerp(p01, p12);
+                (SegmentData::Quadratic { x1: p01.0, y1: p01.1, x: p012.0, y: p012.1 },
+                 SegmentData::Quadratic { x1: p12.0, y1: p12.1, x: p[2].0, y: p[2].1 })
+            }
+            Curve::Cubic(p) => {
+                let p01 = lerp(p[0], p[1]);
+                let p12 = lerp(p[1], p[2]);
+                let p23 = lerp(p[2], p[3]);
+                let p012 = lerp(p01, p12);
+                let p123 = lerp(p12, p23);
+                let p0123 = lerp(p012, p123);
+                (SegmentData::CurveTo {
+                    x1: p01.0, y1: p01.1, x2: p012.0, y2: p012.1, x: p0123.0, y: p0123.1
+                 },
+                 SegmentData::CurveTo {
+                    x1: p123.0, y1: p123.1, x2: p23.0, y2: p23.1, x: p[3].0, y: p[3].1
+                 })
+            }
+            Curve::Arc(ref arc) => {
+                let new_arc = |sweep: f64, (x, y): Point| {
+                    SegmentData::EllipticalArc {
+                        rx: arc.rx,
+                        ry: arc.ry,
+                        x_axis_rotation: arc.phi.to_degrees(),
+                        large_arc: sweep.abs() > f64::consts::PI,
+                        sweep: sweep > 0.0,
+                        x: x,
+                        y: y,
+                    }
+                };
+
+                (new_arc(arc.sweep * t, self.point_at(t)),
+                 new_arc(arc.sweep * (1.0 - t), self.point_at(1.0)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64;
+    use std::str::FromStr;
+
+    use test_utils::ApproxEq;
+    use types::path::{
+        Path,
+        SegmentData,
+    };
+
+    // Compares segment types and end points.
+    fn paths_eq(p1: &Path, p2: &Path) -> bool {
+        p1.d.len() == p2.d.len() && p1.d.iter().zip(&p2.d).all(|(a, b)| {
+               a.cmd() == b.cmd()
+            && a.is_absolute() == b.is_absolute()
+            && a.x().unwrap_or(0.0).approx_eq(&b.x().unwrap_or(0.0), 1e-6)
+            && a.y().unwrap_or(0.0).approx_eq(&b.y().unwrap_or(0.0), 1e-6)
+        })
+    }
+
+    macro_rules! test_length {
+        ($name:ident, $text:expr, $len:expr) => (
+            #[test]
+            fn $name() {
+                let path = Path::from_str($text).unwrap();
+                assert_approx_eq!(path.length(), $len, 1e-6);
+            }
+        )
+    }
+
+    test_length!(length_1, "", 0.0);
+    test_length!(length_2, "M 10 20", 0.0);
+    test_length!(length_3, "M 10 20 l 30 40 h -30 z M 0 0 v 10", 130.0);
+    test_length!(length_4, "M 0 0 C 10 0 20 0 30 0", 30.0);
+    test_length!(length_5, "M 0 0 Q 10 0 20 0 T 40 0", 40.0);
+    test_length!(length_6, "M 0 0 C 10 20 30 -10 40 10", 46.742939067164606);
+    test_length!(length_7, "M 0 0 a 10 10 0 0 1 20 0 a 10 10 0 0 1 -20 0", 20.0 * f64::consts::PI);
+    test_length!(length_8, "M 0 0 A 0 10 0 0 1 20 0", 20.0);
+    // An ellipse circumference.
+    test_length!(length_9, "M 0 0 A 20 10 0 0 1 40 0 A 20 10 0 0 1 0 0", 96.88448220547632);
+
+    macro_rules! test_point {
+        ($name:ident, $text:expr, $len:expr, $x:expr, $y:expr, $angle:expr) => (
+            #[test]
+            fn $name() {
+                let path = Path::from_str($text).unwrap();
+                let (x, y, angle) = path.point_at_length($len).unwrap();
+                assert_approx_eq!(x, $x, 1e-6);
+                assert_approx_eq!(y, $y, 1e-6);
+                assert_approx_eq!(angle, $angle, 1e-6);
+            }
+        )
+    }
+
+    test_point!(point_1, "M 10 20 l 30 40 h -30 z", 25.0, 25.0, 40.0, 53.13010235415598);
+    test_point!(point_2, "M 10 20 l 30 40 h -30 z", 50.0, 40.0, 60.0, 53.13010235415598);
+    test_point!(point_3, "M 10 20 l 30 40 h -30 z", 100.0, 10.0, 40.0, -90.0);
+    test_point!(point_4, "M 10 20 l 30 40 h -30 z", -10.0, 10.0, 20.0, 53.13010235415598);
+    test_point!(point_5, "M 10 20 l 30 40 h -30 z", 1000.0, 10.0, 20.0, -90.0);
+    test_point!(point_6, "M 0 0 a 10 10 0 0 1 20 0", 5.0 * f64::consts::PI, 10.0, -10.0, 0.0);
+    test_point!(point_7, "M 0 0 a 10 10 0 0 0 20 0", 5.0 * f64::consts::PI, 10.0, 10.0, 0.0);
+    test_point!(point_8, "M 0 0 C 10 0 20 0 30 0", 10.0, 10.0, 0.0, 0.0);
+    // Control points are equal to end points.
+    test_point!(point_9, "M 0 0 C 0 0 30 30 30 30", 0.0, 0.0, 0.0, 45.0);
+    test_point!(point_10, "M 10 20 l 0 0 l 10 0", 0.0, 10.0, 20.0, 0.0);
+
+    #[test]
+    fn point_11() {
+        assert_eq!(Path::new().point_at_length(0.0), None);
+        assert_eq!(Path::from_str("M 10 20").unwrap().point_at_length(0.0), None);
+    }
+
+    macro_rules! test_split {
+        ($name:ident, $text:expr, $len:expr, $text1:expr, $text2:expr) => (
+            #[test]
+            fn $name() {
+                let path = Path::from_str($text).unwrap();
+                let (p1, p2) = path.split_at_length($len);
+                assert!(paths_eq(&p1, &Path::from_str($text1).unwrap()), "{}", p1);
+                assert!(paths_eq(&p2, &Path::from_str($text2).unwrap()), "{}", p2);
+            }
+        )
+    }
+
+    test_split!(split_1, "m 10 20 l 30 40 l 10 0", 25.0,
+                "m 10 20 L 25 40",
+                "M 25 40 L 40 60 L 50 60");
+
+    test_split!(split_2, "m 10 20 l 30 40 h -30 z m 100 0 v 10 z", 100.0,
+                "m 10 20 l 30 40 h -30 L 10 40",
+                "M 10 40 L 10 20 M 110 20 L 110 30 Z");
+
+    test_split!(split_3, "M 0 0 L 10 0 Q 20 0 30 0 T 50 0", 35.0,
+                "M 0 0 L 10 0 Q 20 0 30 0 Q 32.5 0 35 0",
+                "M 35 0 Q 42.5 0 50 0");
+
+    test_split!(split_4, "M 0 0 Q 10 0 20 0", 5.0,
+                "M 0 0 Q 2.5 0 5 0",
+                "M 5 0 Q 12.5 0 20 0");
+
+    test_split!(split_5, "M 10 20 L 20 20", 0.0, "", "M 10 20 L 20 20");
+
+    test_split!(split_6, "M 10 20 L 20 20", 10.0, "M 10 20 L 20 20", "");
+
+    #[test]
+    fn split_7() {
+        let path = Path::from_str("M 0 0 A 10 10 0 0 1 20 0").unwrap();
+        let (p1, p2) = path.split_at_length(2.5 * f64::consts::PI);
+        let d = 10.0 / 2.0f64.sqrt();
+
+        let check = |path: &Path, x: f64, y: f64, large_arc: bool| {
+            match path.d[1].data {
+                SegmentData::EllipticalArc { rx, ry, large_arc: la, sweep, .. } => {
+                    assert_approx_eq!(rx, 10.0, 1e-6);
+                    assert_approx_eq!(ry, 10.0, 1e-6);
+                    assert_eq!(la, large_arc);
+                    assert!(sweep);
+                }
+                _ => unreachable!(),
+            }
+
+            assert_approx_eq!(path.d[1].x().unwrap(), x, 1e-6);
+            assert_approx_eq!(path.d[1].y().unwrap(), y, 1e-6);
+        };
+
+        check(&p1, 10.0 - d, -d, false);
+        check(&p2, 20.0, 0.0, false);
+        assert_approx_eq!(p1.length() + p2.length(), path.length(), 1e-6);
+        assert_approx_eq!(p1.length(), 2.5 * f64::consts::PI, 1e-6);
+    }
+
+    // The subpath is closed twice.
+    test_split!(split_8, "M 10 20 l 30 40 h -30 z l 0 10 z m 100 0 v 10 z", 25.0,
+                "M 10 20 L 25 40",
+                "M 25 40 L 40 60 L 10 60 L 10 20 L 10 30 L 10 20 M 110 20 L 110 30 Z");
+}
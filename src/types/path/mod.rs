@@ -7,11 +7,81 @@
 //! [path data]: https://www.w3.org/TR/SVG/paths.html#PathData
 
 pub use self::builder::*;
+pub use self::iter::*;
 pub use self::path::*;
 pub use self::segment::*;
 
+mod arc;
+mod bbox;
 mod builder;
+mod convert;
+mod flatten;
+mod iter;
+mod measure;
 mod parser;
 mod path;
 mod segment;
+mod transform;
 mod writer;
+
+type Point = (f64, f64);
+
+// An iterator over resolved absolute segments with their start points.
+//
+// Returns the current point before the segment, the start point of the current subpath
+// and the segment itself.
+struct PointSegments<'a> {
+    iter: AbsoluteSegments<'a>,
+    prev: Point,
+    start: Point,
+}
+
+impl<'a> Iterator for PointSegments<'a> {
+    type Item = (Point, Point, SegmentData);
+
+    fn next(&mut self) -> Option<(Point, Point, SegmentData)> {
+        let seg = match self.iter.next() {
+            Some(seg) => seg,
+            None => return None,
+        };
+
+        let prev = self.prev;
+        match seg {
+            SegmentData::MoveTo { x, y } => {
+                self.start = (x, y);
+                self.prev = self.start;
+            }
+            SegmentData::ClosePath => {
+                self.prev = self.start;
+            }
+              SegmentData::LineTo { x, y }
+            | SegmentData::CurveTo { x, y, .. }
+            | SegmentData::Quadratic { x, y, .. }
+            | SegmentData::EllipticalArc { x, y, .. } => {
+                self.prev = (x, y);
+            }
+            _ => unreachable!(),
+        }
+
+        Some((prev, self.start, seg))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+fn iter_points<'a>(path: &'a Path) -> PointSegments<'a> {
+    PointSegments {
+        iter: path.iter_absolute(),
+        prev: (0.0, 0.0),
+        start: (0.0, 0.0),
+    }
+}
+
+// Returns control points of a cubic curve that is equal to the quadratic one.
+fn quad_to_cubic(p0: Point, p1: Point, p2: Point) -> (Point, Point) {
+    let c1 = (p0.0 + (p1.0 - p0.0) * 2.0 / 3.0, p0.1 + (p1.1 - p0.1) * 2.0 / 3.0);
+    let c2 = (p2.0 + (p1.0 - p2.0) * 2.0 / 3.0, p2.1 + (p1.1 - p2.1) * 2.0 / 3.0);
+    (c1, c2)
+}
@@ -0,0 +1,256 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use types::{
+    FuzzyEq,
+    Transform,
+};
+use super::{
+    Path,
+    SegmentData,
+};
+
+impl Path {
+    /// Applies the transform to the path.
+    ///
+    /// Absolute segments are transformed as points and relative segments as vectors,
+    /// so the type of each segment is preserved. The only exception are `HorizontalLineTo`
+    /// and `VerticalLineTo`, which are converted into `LineTo` when the transform
+    /// contains a rotation or a skew.
+    ///
+    /// Arc radii and the x-axis rotation are recalculated, and the sweep flag is inverted
+    /// when the transform flips the coordinate system.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::Transform;
+    /// use svgdom::path::Path;
+    ///
+    /// let mut path = Path::from_str("M 10 0 H 20 v 10").unwrap();
+    /// path.transform(&Transform::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0));
+    /// assert_eq!(path.to_string(), "M 0 10 L 0 20 l -10 0");
+    /// ```
+    pub fn transform(&mut self, ts: &Transform) {
+        // Absolute `HorizontalLineTo` and `VerticalLineTo` require the current point.
+        let resolved: Vec<SegmentData> = self.iter_absolute().collect();
+
+        let keep_hv = ts.b.fuzzy_eq(&0.0) && ts.c.fuzzy_eq(&0.0);
+        let flip_sweep = ts.a * ts.d - ts.b * ts.c < 0.0;
+
+        for (i, (seg, resolved)) in self.d.iter_mut().zip(resolved).enumerate() {
+            // A relative MoveTo at the start of the path is treated as absolute.
+            let absolute = seg.absolute || i == 0;
+
+            let apply = |x: f64, y: f64| {
+                if absolute {
+                    ts.apply(x, y)
+                } else {
+                    (ts.a * x + ts.c * y, ts.b * x + ts.d * y)
+                }
+            };
+
+            seg.data = match seg.data {
+                SegmentData::MoveTo { x, y } => {
+                    let (x, y) = apply(x, y);
+                    SegmentData::MoveTo { x: x, y: y }
+                }
+                SegmentData::LineTo { x, y } => {
+                    let (x, y) = apply(x, y);
+                    SegmentData::LineTo { x: x, y: y }
+                }
+                SegmentData::HorizontalLineTo { x } => {
+                    if keep_hv {
+                        let x = if absolute { ts.a * x + ts.e } else { ts.a * x };
+                        SegmentData::HorizontalLineTo { x: x }
+                    } else {
+                        let (x, y) = if absolute { line_end(&resolved) } else { (x, 0.0) };
+                        let (x, y) = apply(x, y);
+                        SegmentData::LineTo { x: x, y: y }
+                    }
+                }
+                SegmentData::VerticalLineTo { y } => {
+                    if keep_hv {
+                        let y = if absolute { ts.d * y + ts.f } else { ts.d * y };
+                        SegmentData::VerticalLineTo { y: y }
+                    } else {
+                        let (x, y) = if absolute { line_end(&resolved) } else { (0.0, y) };
+                        let (x, y) = apply(x, y);
+                        SegmentData::LineTo { x: x, y: y }
+                    }
+                }
+                SegmentData::CurveTo { x1, y1, x2, y2, x, y } => {
+                    let (x1, y1) = apply(x1, y1);
+                    let (x2, y2) = apply(x2, y2);
+                    let (x, y) = apply(x, y);
+                    SegmentData::CurveTo { x1: x1, y1: y1, x2: x2, y2: y2, x: x, y: y }
+                }
+                SegmentData::SmoothCurveTo { x2, y2, x, y } => {
+                    // An affine transform preserves the reflection of the control point.
+                    let (x2, y2) = apply(x2, y2);
+                    let (x, y) = apply(x, y);
+                    SegmentData::SmoothCurveTo { x2: x2, y2: y2, x: x, y: y }
+                }
+                SegmentData::Quadratic { x1, y1, x, y } => {
+                    let (x1, y1) = apply(x1, y1);
+                    let (x, y) = apply(x, y);
+                    SegmentData::Quadratic { x1: x1, y1: y1, x: x, y: y }
+                }
+                SegmentData::SmoothQuadratic { x, y } => {
+                    let (x, y) = apply(x, y);
+                    SegmentData::SmoothQuadratic { x: x, y: y }
+                }
+                SegmentData::EllipticalArc { rx, ry, x_axis_rotation, large_arc, sweep, x, y } => {
+                    let (rx, ry, x_axis_rotation) = transform_arc(ts, rx, ry, x_axis_rotation);
+                    let (x, y) = apply(x, y);
+                    SegmentData::EllipticalArc {
+                        rx: rx,
+                        ry: ry,
+                        x_axis_rotation: x_axis_rotation,
+                        large_arc: large_arc,
+                        sweep: sweep != flip_sweep,
+                        x: x,
+                        y: y,
+                    }
+                }
+                SegmentData::ClosePath => SegmentData::ClosePath,
+            };
+        }
+    }
+}
+
+fn line_end(seg: &SegmentData) -> (f64, f64) {
+    match *seg {
+        SegmentData::LineTo { x, y } => (x, y),
+        _ => unreachable!(),
+    }
+}
+
+// Returns radii and x-axis rotation of the transformed ellipse.
+fn transform_arc(ts: &Transform, rx: f64, ry: f64, x_axis_rotation: f64) -> (f64, f64, f64) {
+    let rx = rx.abs();
+    let ry = ry.abs();
+
+    // The ellipse is a unit circle mapped by `M = ts * rotate(angle) * scale(rx, ry)`.
+    // Its new radii are the square roots of the `M * M^T` eigenvalues.
+    let (sin, cos) = x_axis_rotation.to_radians().sin_cos();
+    let m11 = rx * (ts.a * cos + ts.c * sin);
+    let m21 = rx * (ts.b * cos + ts.d * sin);
+    let m12 = ry * (ts.c * cos - ts.a * sin);
+    let m22 = ry * (ts.d * cos - ts.b * sin);
+
+    let p = m11 * m11 + m12 * m12;
+    let q = m11 * m21 + m12 * m22;
+    let r = m21 * m21 + m22 * m22;
+
+    let h = (((p - r) / 2.0).powi(2) + q * q).sqrt();
+    let major = ((p + r) / 2.0 + h).sqrt();
+    // The product of the radii is equal to the determinant, which is more precise
+    // than the second eigenvalue.
+    let minor = if major > 0.0 { (m11 * m22 - m12 * m21).abs() / major } else { 0.0 };
+    let major_angle = (0.5 * (2.0 * q).atan2(p - r)).to_degrees();
+
+    // Keep the `rx` axis as close as possible to the transformed original one,
+    // so the arc doesn't change under a simple transform.
+    let rx_angle = m21.atan2(m11).to_degrees();
+    let (rx, ry, angle) = if (major_angle - rx_angle).to_radians().cos().abs() >= 0.5f64.sqrt() {
+        (major, minor, major_angle)
+    } else {
+        (minor, major, major_angle + 90.0)
+    };
+
+    let angle = angle + 180.0 * ((rx_angle - angle) / 180.0).round();
+
+    (rx, ry, angle)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use types::Transform;
+    use types::path::{
+        Path,
+        SegmentData,
+    };
+
+    macro_rules! test {
+        ($name:ident, $in_text:expr, $ts:expr, $out_text:expr) => (
+            #[test]
+            fn $name() {
+                let mut path = Path::from_str($in_text).unwrap();
+                path.transform(&$ts);
+                assert_eq_text!(path.to_string(), $out_text);
+            }
+        )
+    }
+
+    test!(translate_1, "M 10 20 L 30 40 h 10 v 10 Z",
+          Transform::new(1.0, 0.0, 0.0, 1.0, 10.0, 20.0),
+          "M 20 40 L 40 60 h 10 v 10 Z");
+
+    test!(translate_2, "m 10 20 l 10 0 z m 5 5 l 1 1", Transform::new(1.0, 0.0, 0.0, 1.0, 5.0, 5.0),
+          "m 15 25 l 10 0 z m 5 5 l 1 1");
+
+    test!(scale_1, "M 10 20 H 30 V 40 h 10 v 10", Transform::new(2.0, 0.0, 0.0, 3.0, 0.0, 0.0),
+          "M 20 60 H 60 V 120 h 20 v 30");
+
+    test!(rotate_1, "M 10 0 H 20 v 10 h 5 V 5", Transform::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0),
+          "M 0 10 L 0 20 l -10 0 l 0 5 L -5 25");
+
+    test!(curve_1, "M 0 0 C 10 0 20 10 20 20 S 30 40 40 40 q 10 0 10 10 t 10 10",
+          Transform::new(2.0, 0.0, 0.0, 2.0, 1.0, 1.0),
+          "M 1 1 C 21 1 41 21 41 41 S 61 81 81 81 q 20 0 20 20 t 20 20");
+
+    test!(arc_1, "M 0 0 A 20 10 0 0 1 40 0 a 20 10 0 0 1 40 0", Transform::new(2.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+          "M 0 0 A 40 10 0 0 1 80 0 a 40 10 0 0 1 80 0");
+
+    // A flipped coordinate system inverts the sweep flag.
+    test!(arc_2, "M 0 0 A 20 10 0 1 1 40 0", Transform::new(1.0, 0.0, 0.0, -1.0, 0.0, 0.0),
+          "M 0 0 A 20 10 0 1 0 40 0");
+
+    test!(arc_3, "M 0 0 A 20 10 30 0 1 40 0", Transform::default(),
+          "M 0 0 A 20 10 30 0 1 40 0");
+
+    #[test]
+    fn arc_4() {
+        let mut path = Path::from_str("M 0 0 A 20 10 30 0 1 40 0").unwrap();
+        let mut ts = Transform::default();
+        ts.rotate(30.0);
+        ts.scale(3.0, 3.0);
+        path.transform(&ts);
+
+        match *path.d[1].data() {
+            SegmentData::EllipticalArc { rx, ry, x_axis_rotation, .. } => {
+                assert_approx_eq!(rx, 60.0, 1e-9);
+                assert_approx_eq!(ry, 30.0, 1e-9);
+                assert_approx_eq!(x_axis_rotation, 60.0, 1e-9);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn skew_1() {
+        // The transformed path must have the same shape as the original one
+        // rendered with the transform.
+        let text = "M 10 20 A 30 10 45 1 0 40 30 h 20 v -10 a 5 8 -20 0 1 -20 0 \
+                    Q 60 80 20 60 t -10 10 C 0 50 10 40 20 30 s 10 10 0 20 Z";
+        let path = Path::from_str(text).unwrap();
+
+        let mut ts = Transform::default();
+        ts.translate(10.0, -5.0);
+        ts.rotate(25.0);
+        ts.skew_x(20.0);
+        ts.scale(1.5, -0.7);
+
+        let bbox = path.bbox_with_transform(&ts).unwrap();
+
+        let mut new_path = path.clone();
+        new_path.transform(&ts);
+
+        // The second arc has scaled up radii, which are calculated with a lower precision.
+        assert_approx_eq!(new_path.bbox().unwrap(), bbox, 1e-6);
+    }
+}
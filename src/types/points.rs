@@ -0,0 +1,178 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use svgparser::{
+    Error as ParseError,
+    Stream,
+    StrSpan,
+    StreamExt,
+};
+
+use {
+    ParseFromSpan,
+    WriteBuffer,
+    WriteOptions,
+    ToStringWithOptions,
+};
+use super::number::FuzzyEq;
+use super::path;
+use super::write_list;
+
+/// Representation of the [`points`] attribute value.
+///
+/// [`points`]: https://www.w3.org/TR/SVG/shapes.html#PolylineElementPointsAttribute
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct Points(pub Vec<(f64, f64)>);
+
+impl Points {
+    /// Constructs a new, empty points list.
+    pub fn new() -> Points {
+        Points(Vec::new())
+    }
+
+    /// Converts points into a path.
+    ///
+    /// `closed` should be `true` for the `polygon` element and `false` for the `polyline`.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::Points;
+    ///
+    /// let points = Points(vec![(10.0, 20.0), (30.0, 40.0)]);
+    /// assert_eq!(points.to_path(true).to_string(), "M 10 20 L 30 40 Z");
+    /// ```
+    pub fn to_path(&self, closed: bool) -> path::Path {
+        let mut builder = path::Builder::with_capacity(self.0.len() + 1);
+
+        for (i, &(x, y)) in self.0.iter().enumerate() {
+            builder = if i == 0 {
+                builder.move_to(x, y)
+            } else {
+                builder.line_to(x, y)
+            };
+        }
+
+        if closed && !self.0.is_empty() {
+            builder = builder.close_path();
+        }
+
+        builder.finalize()
+    }
+
+    /// Compares two lists using fuzzy float compare algorithm.
+    pub fn fuzzy_eq(&self, other: &Points) -> bool {
+           self.0.len() == other.0.len()
+        && self.0.iter().zip(&other.0).all(|(a, b)| a.0.fuzzy_eq(&b.0) && a.1.fuzzy_eq(&b.1))
+    }
+}
+
+impl Deref for Points {
+    type Target = Vec<(f64, f64)>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Points {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<(f64, f64)>> for Points {
+    fn from(list: Vec<(f64, f64)>) -> Points {
+        Points(list)
+    }
+}
+
+impl_from_str!(Points);
+
+impl ParseFromSpan for Points {
+    type Err = ParseError;
+
+    /// Parses a list of coordinate pairs.
+    ///
+    /// If the list has an odd number of coordinates, the last one is ignored,
+    /// as described in the SVG 2 spec.
+    fn from_span(span: StrSpan) -> Result<Points, ParseError> {
+        let mut s = Stream::from_span(span);
+        s.skip_spaces();
+
+        let mut list = Vec::new();
+        let mut prev = None;
+        while !s.at_end() {
+            let n = s.parse_list_number()?;
+            match prev.take() {
+                Some(x) => list.push((x, n)),
+                None => prev = Some(n),
+            }
+        }
+
+        Ok(Points(list))
+    }
+}
+
+impl WriteBuffer for Points {
+    fn write_buf_opt(&self, opt: &WriteOptions, buf: &mut Vec<u8>) {
+        let mut numbers = Vec::with_capacity(self.0.len() * 2);
+        for &(x, y) in &self.0 {
+            numbers.push(x);
+            numbers.push(y);
+        }
+
+        write_list(&numbers, opt, buf);
+    }
+}
+
+impl_display!(Points);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+    use ListSeparator;
+
+    macro_rules! test_parse {
+        ($name:ident, $text:expr, $result:expr) => (
+            #[test]
+            fn $name() {
+                assert_eq!(Points::from_str($text).unwrap(), Points($result));
+            }
+        )
+    }
+
+    test_parse!(parse_1, "", vec![]);
+    test_parse!(parse_2, "10 20 30 40", vec![(10.0, 20.0), (30.0, 40.0)]);
+    test_parse!(parse_3, " 10,20, 30-40 ", vec![(10.0, 20.0), (30.0, -40.0)]);
+    test_parse!(parse_4, "10 20 30", vec![(10.0, 20.0)]);
+
+    #[test]
+    fn parse_err_1() {
+        assert!(Points::from_str("10 20 q").is_err());
+    }
+
+    #[test]
+    fn write_1() {
+        let points = Points(vec![(0.5, 10.0), (-0.5, 20.0)]);
+
+        let mut opt = WriteOptions::default();
+        assert_eq!(points.to_string_with_opt(&opt), "0.5 10 -0.5 20");
+
+        opt.list_separator = ListSeparator::Comma;
+        opt.remove_leading_zero = true;
+        assert_eq!(points.to_string_with_opt(&opt), ".5,10,-.5,20");
+    }
+
+    #[test]
+    fn to_path_1() {
+        let points = Points(vec![(10.0, 20.0), (30.0, 40.0), (50.0, 60.0)]);
+        assert_eq!(points.to_path(false).to_string(), "M 10 20 L 30 40 L 50 60");
+        assert_eq!(Points::new().to_path(true).to_string(), "");
+    }
+}
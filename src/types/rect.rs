@@ -0,0 +1,140 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt;
+use std::str::FromStr;
+
+use svgparser::{
+    Error as ParseError,
+    ErrorKind as ParseErrorKind,
+    Stream,
+    StrSpan,
+    StreamExt,
+};
+
+use {
+    ParseFromSpan,
+    WriteBuffer,
+    WriteOptions,
+    ToStringWithOptions,
+};
+use super::number::FuzzyEq;
+use super::write_list;
+
+/// Representation of a rectangle.
+///
+/// Used by the `viewBox` attribute and bounding boxes.
+#[derive(Clone,Copy,PartialEq,Debug)]
+#[allow(missing_docs)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    /// Constructs a new rectangle.
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            x: x,
+            y: y,
+            width: width,
+            height: height,
+        }
+    }
+
+    /// Returns the right edge position.
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    /// Returns the bottom edge position.
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    /// Returns the smallest rectangle that contains both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+        Rect::new(x, y, right - x, bottom - y)
+    }
+
+    /// Compares two rectangles using fuzzy float compare algorithm.
+    pub fn fuzzy_eq(&self, other: &Rect) -> bool {
+           self.x.fuzzy_eq(&other.x)
+        && self.y.fuzzy_eq(&other.y)
+        && self.width.fuzzy_eq(&other.width)
+        && self.height.fuzzy_eq(&other.height)
+    }
+}
+
+impl_from_str!(Rect);
+
+impl ParseFromSpan for Rect {
+    type Err = ParseError;
+
+    /// Parses a rectangle in the `viewBox` format: `<min-x> <min-y> <width> <height>`.
+    ///
+    /// A negative width or height is an error.
+    fn from_span(span: StrSpan) -> Result<Rect, ParseError> {
+        let mut s = Stream::from_span(span);
+        s.skip_spaces();
+
+        let x = s.parse_list_number()?;
+        let y = s.parse_list_number()?;
+        let width = s.parse_list_number()?;
+        let height = s.parse_list_number()?;
+
+        if !s.at_end() || width < 0.0 || height < 0.0 {
+            return Err(ParseErrorKind::InvalidAttributeValue(s.gen_error_pos()).into());
+        }
+
+        Ok(Rect::new(x, y, width, height))
+    }
+}
+
+impl WriteBuffer for Rect {
+    fn write_buf_opt(&self, opt: &WriteOptions, buf: &mut Vec<u8>) {
+        write_list(&[self.x, self.y, self.width, self.height], opt, buf);
+    }
+}
+
+impl_display!(Rect);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+    use ListSeparator;
+
+    #[test]
+    fn parse_1() {
+        assert_eq!(Rect::from_str("0 10.5 20,30").unwrap(), Rect::new(0.0, 10.5, 20.0, 30.0));
+    }
+
+    #[test]
+    fn parse_err_1() {
+        assert!(Rect::from_str("0 0 10").is_err());
+        assert!(Rect::from_str("0 0 10 10 10").is_err());
+        assert!(Rect::from_str("0 0 -10 10").is_err());
+    }
+
+    #[test]
+    fn union_1() {
+        let r = Rect::new(0.0, 10.0, 20.0, 10.0).union(&Rect::new(-5.0, 15.0, 10.0, 20.0));
+        assert_eq!(r, Rect::new(-5.0, 10.0, 25.0, 25.0));
+    }
+
+    #[test]
+    fn write_1() {
+        let mut opt = WriteOptions::default();
+        opt.list_separator = ListSeparator::Comma;
+        opt.remove_leading_zero = true;
+        assert_eq!(Rect::new(0.5, 0.0, 20.0, 30.0).to_string_with_opt(&opt), ".5,0,20,30");
+    }
+}
@@ -96,6 +96,119 @@ impl Transform {
         self.f = tm.d[2][1];
     }
 
+    /// Prepends transform to the current transform.
+    ///
+    /// Unlike [`append`], the `t` transform will be applied after the current one.
+    ///
+    /// [`append`]: #method.append
+    pub fn prepend(&mut self, t: &Transform) {
+        *self = t.multiply(self);
+    }
+
+    /// Returns a product of the current transform and `t`.
+    ///
+    /// The same as [`append`], but doesn't modify the current transform.
+    ///
+    /// [`append`]: #method.append
+    pub fn multiply(&self, t: &Transform) -> Transform {
+        let mut ts = *self;
+        ts.append(t);
+        ts
+    }
+
+    /// Returns the determinant of the transform matrix.
+    pub fn determinant(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// Returns an inverted transform.
+    ///
+    /// Returns `None` if the transform is not invertible, like `scale(0)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::Transform;
+    ///
+    /// let ts = Transform::new(2.0, 0.0, 0.0, 2.0, 10.0, 20.0);
+    /// let (x, y) = ts.apply(5.0, 5.0);
+    /// assert_eq!(ts.invert().unwrap().apply(x, y), (5.0, 5.0));
+    /// ```
+    pub fn invert(&self) -> Option<Transform> {
+        let det = self.determinant();
+        if det.fuzzy_eq(&0.0) || !det.is_finite() {
+            return None;
+        }
+
+        Some(Transform::new(
+             self.d / det,
+            -self.b / det,
+            -self.c / det,
+             self.a / det,
+            (self.c * self.f - self.d * self.e) / det,
+            (self.b * self.e - self.a * self.f) / det,
+        ))
+    }
+
+    /// Decomposes the transform into translate, rotate, skew and scale parts.
+    ///
+    /// Unlike [`get_rotate`], [`get_scale`] and [`get_skew`], the result can be composed back
+    /// into the same transform using [`DecomposedTransform::to_transform`].
+    ///
+    /// Exact only for invertible transforms. For singular ones,
+    /// like `scale(0 1)`, some parts will be lost.
+    ///
+    /// # Examples
+    /// ```
+    /// use svgdom::Transform;
+    ///
+    /// let mut ts = Transform::default();
+    /// ts.translate(10.0, 20.0);
+    /// ts.rotate(30.0);
+    /// ts.scale(2.0, 3.0);
+    ///
+    /// let parts = ts.decompose();
+    /// assert_eq!(parts.translate_x, 10.0);
+    /// assert!((parts.rotate - 30.0).abs() < 1e-10);
+    /// assert!(parts.to_transform().fuzzy_eq(&ts));
+    /// ```
+    ///
+    /// [`get_rotate`]: #method.get_rotate
+    /// [`get_scale`]: #method.get_scale
+    /// [`get_skew`]: #method.get_skew
+    /// [`DecomposedTransform::to_transform`]: struct.DecomposedTransform.html#method.to_transform
+    pub fn decompose(&self) -> DecomposedTransform {
+        let rad = 180.0 / f64::consts::PI;
+
+        // The matrix is decomposed as rotate * skewX * scale, where the skewX * scale part
+        // is an upper triangular matrix: [sx, tan(skew) * sy; 0, sy].
+        let scale_x = (self.a * self.a + self.b * self.b).sqrt();
+
+        let (rotate, scale_y, skew_x) = if scale_x.fuzzy_eq(&0.0) {
+            // The first column is zero, so only the second one matters.
+            let sy = (self.c * self.c + self.d * self.d).sqrt();
+            ((-self.c).atan2(self.d) * rad, sy, 0.0)
+        } else {
+            let det = self.determinant();
+            let scale_y = det / scale_x;
+            let skew = if scale_y.fuzzy_eq(&0.0) {
+                0.0
+            } else {
+                ((self.a * self.c + self.b * self.d) / det).atan() * rad
+            };
+
+            (self.b.atan2(self.a) * rad, scale_y, skew)
+        };
+
+        DecomposedTransform {
+            translate_x: self.e,
+            translate_y: self.f,
+            rotate: rotate,
+            skew_x: skew_x,
+            scale_x: scale_x,
+            scale_y: scale_y,
+        }
+    }
+
     fn to_matrix(&self) -> TransformMatrix {
         let mut tm = TransformMatrix::default();
         tm.d[0][0] = self.a;
@@ -225,6 +338,40 @@ impl Transform {
     }
 }
 
+/// A [`Transform`] decomposed into primitive operations.
+///
+/// The operations are applied in the following order:
+/// `translate(translate_x translate_y) rotate(rotate) skewX(skew_x) scale(scale_x scale_y)`.
+///
+/// [`Transform`]: struct.Transform.html
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct DecomposedTransform {
+    #[allow(missing_docs)]
+    pub translate_x: f64,
+    #[allow(missing_docs)]
+    pub translate_y: f64,
+    /// Rotation angle in degrees.
+    pub rotate: f64,
+    /// Skew angle along the X axis in degrees.
+    pub skew_x: f64,
+    #[allow(missing_docs)]
+    pub scale_x: f64,
+    /// Scale along the Y axis. Negative for transforms with a reflection.
+    pub scale_y: f64,
+}
+
+impl DecomposedTransform {
+    /// Composes parts back into a transform.
+    pub fn to_transform(&self) -> Transform {
+        let mut ts = Transform::default();
+        ts.translate(self.translate_x, self.translate_y);
+        ts.rotate(self.rotate);
+        ts.skew_x(self.skew_x);
+        ts.scale(self.scale_x, self.scale_y);
+        ts
+    }
+}
+
 impl Default for Transform {
     fn default() -> Transform {
         Transform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
@@ -549,4 +696,65 @@ mod tests {
         Transform::from_str("scale(1 -1)").unwrap(), true,
         "scale(1 -1)"
     );
+
+    #[test]
+    fn invert_1() {
+        let ts = Transform::from_str("translate(10 20) rotate(30) skewX(15) scale(2 -3)").unwrap();
+        let inv = ts.invert().unwrap();
+        assert_approx_eq!(ts.multiply(&inv), Transform::default(), 1e-10);
+        assert_approx_eq!(inv.multiply(&ts), Transform::default(), 1e-10);
+    }
+
+    #[test]
+    fn invert_2() {
+        assert_eq!(Transform::new(0.0, 0.0, 0.0, 1.0, 10.0, 0.0).invert(), None);
+        assert_eq!(Transform::new(1.0, 2.0, 2.0, 4.0, 0.0, 0.0).invert(), None);
+    }
+
+    #[test]
+    fn prepend_1() {
+        let mut ts1 = Transform::new(2.0, 0.0, 0.0, 2.0, 0.0, 0.0);
+        ts1.prepend(&Transform::new(1.0, 0.0, 0.0, 1.0, 10.0, 20.0));
+        assert_eq!(ts1, Transform::new(2.0, 0.0, 0.0, 2.0, 10.0, 20.0));
+
+        let mut ts2 = Transform::new(2.0, 0.0, 0.0, 2.0, 0.0, 0.0);
+        ts2.append(&Transform::new(1.0, 0.0, 0.0, 1.0, 10.0, 20.0));
+        assert_eq!(ts2, Transform::new(2.0, 0.0, 0.0, 2.0, 20.0, 40.0));
+    }
+
+    macro_rules! test_decompose {
+        ($name:ident, $text:expr) => (
+            #[test]
+            fn $name() {
+                let ts = Transform::from_str($text).unwrap();
+                let ts2 = ts.decompose().to_transform();
+                assert_approx_eq!(ts, ts2, 1e-10);
+            }
+        )
+    }
+
+    test_decompose!(decompose_1, "translate(10 20)");
+    test_decompose!(decompose_2, "rotate(30)");
+    test_decompose!(decompose_3, "rotate(-150) scale(2 3)");
+    test_decompose!(decompose_4, "skewX(30) rotate(45)");
+    test_decompose!(decompose_5, "skewY(30) scale(-1 1)");
+    test_decompose!(decompose_6, "matrix(0 0 2 3 10 20)");
+    test_decompose!(decompose_7, "translate(10 20) rotate(30) skewX(15) scale(2 -3)");
+
+    #[test]
+    fn decompose_parts_1() {
+        let parts = Transform::from_str("translate(10 20) rotate(90) skewX(45) scale(2 3)")
+            .unwrap().decompose();
+        let expected = DecomposedTransform {
+            translate_x: 10.0,
+            translate_y: 20.0,
+            rotate: 90.0,
+            skew_x: 45.0,
+            scale_x: 2.0,
+            scale_y: 3.0,
+        };
+        assert_approx_eq!(parts.to_transform(), expected.to_transform(), 1e-10);
+        assert_approx_eq!(parts.rotate, 90.0, 1e-10);
+        assert_approx_eq!(parts.skew_x, 45.0, 1e-10);
+    }
 }
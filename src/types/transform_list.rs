@@ -0,0 +1,324 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use svgparser::{
+    Error as ParseError,
+    ErrorKind as ParseErrorKind,
+    Stream,
+    StrSpan,
+    StreamExt,
+};
+
+use {
+    ParseFromSpan,
+    WriteBuffer,
+    WriteOptions,
+    ToStringWithOptions,
+};
+use super::number::{
+    write_num,
+    FuzzyEq,
+};
+use super::Transform;
+
+/// A primitive operation of the [`TransformList`].
+///
+/// [`TransformList`]: struct.TransformList.html
+#[derive(Clone,Copy,PartialEq,Debug)]
+#[allow(missing_docs)]
+pub enum TransformListItem {
+    Matrix(Transform),
+    Translate { tx: f64, ty: f64 },
+    Scale { sx: f64, sy: f64 },
+    /// Rotation by `angle` degrees about the point (`cx`, `cy`).
+    Rotate { angle: f64, cx: f64, cy: f64 },
+    SkewX { angle: f64 },
+    SkewY { angle: f64 },
+}
+
+impl TransformListItem {
+    /// Converts an operation into a transform matrix.
+    pub fn to_transform(&self) -> Transform {
+        let mut ts = Transform::default();
+        match *self {
+            TransformListItem::Matrix(m) => ts = m,
+            TransformListItem::Translate { tx, ty } => ts.translate(tx, ty),
+            TransformListItem::Scale { sx, sy } => ts.scale(sx, sy),
+            TransformListItem::Rotate { angle, cx, cy } => {
+                ts.translate(cx, cy);
+                ts.rotate(angle);
+                ts.translate(-cx, -cy);
+            }
+            TransformListItem::SkewX { angle } => ts.skew_x(angle),
+            TransformListItem::SkewY { angle } => ts.skew_y(angle),
+        }
+
+        ts
+    }
+
+    /// Compares two operations using fuzzy float compare algorithm.
+    pub fn fuzzy_eq(&self, other: &TransformListItem) -> bool {
+        use self::TransformListItem as Item;
+
+        match (*self, *other) {
+            (Item::Matrix(ref a), Item::Matrix(ref b)) => a.fuzzy_eq(b),
+            (Item::Translate { tx, ty }, Item::Translate { tx: tx2, ty: ty2 }) => {
+                tx.fuzzy_eq(&tx2) && ty.fuzzy_eq(&ty2)
+            }
+            (Item::Scale { sx, sy }, Item::Scale { sx: sx2, sy: sy2 }) => {
+                sx.fuzzy_eq(&sx2) && sy.fuzzy_eq(&sy2)
+            }
+            (Item::Rotate { angle, cx, cy }, Item::Rotate { angle: a2, cx: cx2, cy: cy2 }) => {
+                angle.fuzzy_eq(&a2) && cx.fuzzy_eq(&cx2) && cy.fuzzy_eq(&cy2)
+            }
+              (Item::SkewX { angle }, Item::SkewX { angle: a2 })
+            | (Item::SkewY { angle }, Item::SkewY { angle: a2 }) => angle.fuzzy_eq(&a2),
+            _ => false,
+        }
+    }
+}
+
+/// Representation of the [`<transform-list>`] type, which retains all operations as written.
+///
+/// Unlike [`Transform`], which stores only the resulting matrix.
+///
+/// [`<transform-list>`]: https://www.w3.org/TR/SVG/coords.html#TransformAttribute
+/// [`Transform`]: struct.Transform.html
+#[derive(Clone,PartialEq,Debug,Default)]
+pub struct TransformList(pub Vec<TransformListItem>);
+
+impl TransformList {
+    /// Constructs a new, empty transform list.
+    pub fn new() -> TransformList {
+        TransformList(Vec::new())
+    }
+
+    /// Flattens the list into a single transform matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use svgdom::{Transform, TransformList};
+    ///
+    /// let list = TransformList::from_str("translate(10) scale(2)").unwrap();
+    /// assert_eq!(list.to_transform(), Transform::new(2.0, 0.0, 0.0, 2.0, 10.0, 0.0));
+    /// ```
+    pub fn to_transform(&self) -> Transform {
+        let mut ts = Transform::default();
+        for item in &self.0 {
+            ts.append(&item.to_transform());
+        }
+
+        ts
+    }
+
+    /// Compares two lists using fuzzy float compare algorithm.
+    pub fn fuzzy_eq(&self, other: &TransformList) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().zip(&other.0).all(|(a, b)| a.fuzzy_eq(b))
+    }
+}
+
+impl Deref for TransformList {
+    type Target = Vec<TransformListItem>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for TransformList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Transform> for TransformList {
+    fn from(ts: Transform) -> TransformList {
+        TransformList(vec![TransformListItem::Matrix(ts)])
+    }
+}
+
+impl_from_str!(TransformList);
+
+impl ParseFromSpan for TransformList {
+    type Err = ParseError;
+
+    fn from_span(span: StrSpan) -> Result<TransformList, ParseError> {
+        let mut s = Stream::from_span(span);
+        let mut list = Vec::new();
+
+        s.skip_spaces();
+        while !s.at_end() {
+            list.push(parse_item(&mut s)?);
+
+            s.skip_spaces();
+            if s.is_curr_byte_eq(b',') {
+                s.advance(1);
+                s.skip_spaces();
+            }
+        }
+
+        Ok(TransformList(list))
+    }
+}
+
+fn parse_item(s: &mut Stream) -> Result<TransformListItem, ParseError> {
+    let start = s.pos();
+    let name = s.consume_name()?;
+    s.skip_spaces();
+    s.consume_byte(b'(')?;
+    s.skip_spaces();
+
+    // Parses an optional argument.
+    fn parse_opt(s: &mut Stream) -> Result<Option<f64>, ParseError> {
+        s.skip_spaces();
+        if s.is_curr_byte_eq(b')') {
+            Ok(None)
+        } else {
+            s.parse_list_number().map(Some)
+        }
+    }
+
+    let item = match name.to_str() {
+        "matrix" => {
+            TransformListItem::Matrix(Transform::new(
+                s.parse_list_number()?,
+                s.parse_list_number()?,
+                s.parse_list_number()?,
+                s.parse_list_number()?,
+                s.parse_list_number()?,
+                s.parse_list_number()?,
+            ))
+        }
+        "translate" => {
+            let tx = s.parse_list_number()?;
+            let ty = parse_opt(s)?.unwrap_or(0.0);
+            TransformListItem::Translate { tx: tx, ty: ty }
+        }
+        "scale" => {
+            let sx = s.parse_list_number()?;
+            let sy = parse_opt(s)?.unwrap_or(sx);
+            TransformListItem::Scale { sx: sx, sy: sy }
+        }
+        "rotate" => {
+            let angle = s.parse_list_number()?;
+            let (cx, cy) = match parse_opt(s)? {
+                Some(cx) => (cx, s.parse_list_number()?),
+                None => (0.0, 0.0),
+            };
+            TransformListItem::Rotate { angle: angle, cx: cx, cy: cy }
+        }
+        "skewX" => TransformListItem::SkewX { angle: s.parse_list_number()? },
+        "skewY" => TransformListItem::SkewY { angle: s.parse_list_number()? },
+        _ => {
+            let pos = s.gen_error_pos_from(start);
+            return Err(ParseErrorKind::InvalidTransform(pos).into());
+        }
+    };
+
+    s.skip_spaces();
+    s.consume_byte(b')')?;
+
+    Ok(item)
+}
+
+impl WriteBuffer for TransformListItem {
+    fn write_buf_opt(&self, opt: &WriteOptions, buf: &mut Vec<u8>) {
+        let rm = opt.remove_leading_zero;
+
+        let (name, args, len): (&[u8], [f64; 6], usize) = match *self {
+            TransformListItem::Matrix(ts) => {
+                (b"matrix", [ts.a, ts.b, ts.c, ts.d, ts.e, ts.f], 6)
+            }
+            TransformListItem::Translate { tx, ty } => {
+                let len = if ty.fuzzy_eq(&0.0) { 1 } else { 2 };
+                (b"translate", [tx, ty, 0.0, 0.0, 0.0, 0.0], len)
+            }
+            TransformListItem::Scale { sx, sy } => {
+                let len = if sx.fuzzy_eq(&sy) { 1 } else { 2 };
+                (b"scale", [sx, sy, 0.0, 0.0, 0.0, 0.0], len)
+            }
+            TransformListItem::Rotate { angle, cx, cy } => {
+                let len = if cx.fuzzy_eq(&0.0) && cy.fuzzy_eq(&0.0) { 1 } else { 3 };
+                (b"rotate", [angle, cx, cy, 0.0, 0.0, 0.0], len)
+            }
+            TransformListItem::SkewX { angle } => (b"skewX", [angle, 0.0, 0.0, 0.0, 0.0, 0.0], 1),
+            TransformListItem::SkewY { angle } => (b"skewY", [angle, 0.0, 0.0, 0.0, 0.0, 0.0], 1),
+        };
+
+        buf.extend_from_slice(name);
+        buf.push(b'(');
+        for (i, n) in args[..len].iter().enumerate() {
+            if i != 0 {
+                buf.push(b' ');
+            }
+
+            write_num(n, rm, buf);
+        }
+        buf.push(b')');
+    }
+}
+
+impl WriteBuffer for TransformList {
+    fn write_buf_opt(&self, opt: &WriteOptions, buf: &mut Vec<u8>) {
+        for (i, item) in self.0.iter().enumerate() {
+            if i != 0 {
+                buf.push(b' ');
+            }
+
+            item.write_buf_opt(opt, buf);
+        }
+    }
+}
+
+impl_display!(TransformList);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+
+    macro_rules! test_resave {
+        ($name:ident, $text:expr, $result:expr) => (
+            #[test]
+            fn $name() {
+                let list = TransformList::from_str($text).unwrap();
+                assert_eq!(list.to_string(), $result);
+            }
+        )
+    }
+
+    test_resave!(resave_1, "", "");
+    test_resave!(resave_2, "translate(10) rotate(45 5 5) scale(2)",
+                 "translate(10) rotate(45 5 5) scale(2)");
+    test_resave!(resave_3, " translate(10,20),scale( 2 3 ) rotate(30)skewX(5) skewY(-5) ",
+                 "translate(10 20) scale(2 3) rotate(30) skewX(5) skewY(-5)");
+    test_resave!(resave_4, "matrix(1 0 0 1 10 20)", "matrix(1 0 0 1 10 20)");
+
+    #[test]
+    fn parse_1() {
+        let list = TransformList::from_str("rotate(45 5 6)").unwrap();
+        assert_eq!(list[0], TransformListItem::Rotate { angle: 45.0, cx: 5.0, cy: 6.0 });
+    }
+
+    #[test]
+    fn parse_err_1() {
+        assert!(TransformList::from_str("rotate(45 5)").is_err());
+        assert!(TransformList::from_str("scale(2").is_err());
+        assert!(TransformList::from_str("move(2)").is_err());
+        assert!(TransformList::from_str("matrix(1 0 0 1)").is_err());
+    }
+
+    #[test]
+    fn to_transform_1() {
+        let text = "translate(10) rotate(45 5 5) scale(2) skewX(10) matrix(1 2 3 4 5 6)";
+        let list = TransformList::from_str(text).unwrap();
+        let ts = Transform::from_str(text).unwrap();
+        assert!(list.to_transform().fuzzy_eq(&ts));
+    }
+}
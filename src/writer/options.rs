@@ -133,6 +133,17 @@ pub struct WriteOptions {
     /// Default: disabled
     pub trim_hex_colors: bool,
 
+    /// Use CSS color keywords when they are shorter than the hex notation.
+    ///
+    /// Together with `trim_hex_colors` produces the shortest color notation.
+    ///
+    /// # Examples
+    ///
+    /// `#ff0000` -> `red`, `#ffffff` -> `white`, `#d2b48c` -> `tan`
+    ///
+    /// Default: disabled
+    pub use_color_keywords: bool,
+
     /// Write hidden attributes.
     ///
     /// `libsvgdom` support invisible attributes, which can be dumped to output using this option.
@@ -236,6 +247,7 @@ impl Default for WriteOptions {
             attributes_indent: Indent::None,
             use_single_quote: false,
             trim_hex_colors: false,
+            use_color_keywords: false,
             write_hidden_attributes: false,
             remove_leading_zero: false,
             use_compact_path_notation: false,
@@ -5,13 +5,27 @@
 #[macro_use]
 extern crate svgdom;
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use svgdom::{
     AttributeId as AId,
     AttributeValue,
+    Change,
+    Color,
+    Conflict,
     Document,
     ElementId as EId,
+    History,
+    Length,
+    LengthUnit,
+    Rect,
+    Transform,
+    Mutation,
+    Name,
     WriteOptions,
     ToStringWithOptions,
+    ViolationKind,
     ChainedErrorExt,
 };
 
@@ -480,3 +494,1377 @@ fn remove_attr_2() {
     // must panic
     rect.attributes_mut().retain(|a| !a.has_id(AId::XlinkHref));
 }
+
+#[test]
+fn import_node_1() {
+    let doc1 = Document::from_str(
+"<svg>
+    <g id='g1' stroke-width='5'>
+        <rect id='rect1'/>
+    </g>
+</svg>").unwrap();
+
+    let mut doc2 = Document::from_str("<svg/>").unwrap();
+
+    let g = doc1.descendants().find(|n| n.is_tag_name(EId::G)).unwrap();
+
+    // shallow copy
+    let new_g = doc2.import_node(&g, false).unwrap();
+    assert_eq!(new_g.has_children(), false);
+
+    // deep copy
+    let new_g = doc2.import_node(&g, true).unwrap();
+    doc2.svg_element().unwrap().append(&new_g);
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+    assert_eq_text!(doc2.to_string_with_opt(&opt),
+"<svg>
+    <g id='g1' stroke-width='5'>
+        <rect id='rect1'/>
+    </g>
+</svg>
+");
+}
+
+#[test]
+fn import_node_2() {
+    let doc1 = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'>
+        <stop offset='0'/>
+    </linearGradient>
+    <linearGradient id='lg2' xlink:href='#lg1'/>
+    <clipPath id='clip1'>
+        <rect width='10' height='10'/>
+    </clipPath>
+    <g id='g1' clip-path='url(#clip1)'>
+        <rect id='rect1' fill='url(#lg2)'/>
+    </g>
+</svg>").unwrap();
+
+    let mut doc2 = Document::from_str(
+"<svg>
+    <defs>
+        <linearGradient id='lg1'/>
+    </defs>
+</svg>").unwrap();
+
+    let g = doc1.descendants().find(|n| n.is_tag_name(EId::G)).unwrap();
+    let new_g = doc2.import_node(&g, true).unwrap();
+    doc2.svg_element().unwrap().append(&new_g);
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+    assert_eq_text!(doc2.to_string_with_opt(&opt),
+"<svg>
+    <defs>
+        <linearGradient id='lg1'/>
+        <clipPath id='clip1'>
+            <rect height='10' width='10'/>
+        </clipPath>
+        <linearGradient id='lg2' xlink:href='#lg1_1'/>
+        <linearGradient id='lg1_1'>
+            <stop offset='0'/>
+        </linearGradient>
+    </defs>
+    <g id='g1' clip-path='url(#clip1)'>
+        <rect id='rect1' fill='url(#lg2)'/>
+    </g>
+</svg>
+");
+
+    // source document must not be changed
+    let lg1 = doc1.descendants().find(|n| *n.id() == "lg1").unwrap();
+    assert_eq!(lg1.uses_count(), 1);
+}
+
+#[test]
+fn import_node_3() {
+    let mut doc = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'/>
+    <rect id='rect1' fill='url(#lg1)'/>
+</svg>").unwrap();
+
+    // import from the same document
+    let rect = doc.descendants().find(|n| n.is_tag_name(EId::Rect)).unwrap();
+    let new_rect = doc.import_node(&rect, true).unwrap();
+    doc.svg_element().unwrap().append(&new_rect);
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+    assert_eq_text!(doc.to_string_with_opt(&opt),
+"<svg>
+    <linearGradient id='lg1'/>
+    <rect id='rect1' fill='url(#lg1)'/>
+    <rect id='rect1_1' fill='url(#lg1)'/>
+</svg>
+");
+}
+
+#[test]
+fn import_node_4() {
+    let doc1 = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'/>
+    <rect fill='url(#lg1)'/>
+</svg>").unwrap();
+
+    let mut doc2 = Document::new();
+
+    // referenced elements can't be imported without an 'svg' element
+    let rect = doc1.descendants().find(|n| n.is_tag_name(EId::Rect)).unwrap();
+    assert_eq!(doc2.import_node(&rect, true).unwrap_err().full_chain(),
+               "Error: the document does not have an SVG element");
+}
+
+#[test]
+fn import_node_6() {
+    use svgdom::Attribute;
+
+    let doc1 = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'/>
+    <linearGradient id='lg2' xlink:href='#lg1'/>
+    <rect fill='url(#lg1)'/>
+</svg>").unwrap();
+
+    // Create a crosslink, which can't be done via `set_attribute`.
+    let mut lg1 = doc1.descendants().find(|n| *n.id() == "lg1").unwrap();
+    let lg2 = doc1.descendants().find(|n| *n.id() == "lg2").unwrap();
+    lg1.attributes_mut().insert_impl(Attribute::new(AId::XlinkHref, AttributeValue::Link(lg2)));
+
+    let mut doc2 = Document::from_str("<svg/>").unwrap();
+
+    // the document must not be changed on error
+    let rect = doc1.descendants().find(|n| n.is_tag_name(EId::Rect)).unwrap();
+    assert!(doc2.import_node(&rect, true).is_err());
+    assert_eq_text!(doc2.to_string(), "<svg/>\n");
+
+    // The crosslink doesn't have a back reference, so it must be removed manually.
+    lg1.attributes_mut().remove_impl(AId::XlinkHref);
+}
+
+#[test]
+#[should_panic]
+fn import_node_5() {
+    let mut doc = Document::new();
+    let other = Document::from_str("<svg/>").unwrap();
+    let _ = doc.import_node(&other.root(), true);
+}
+
+#[test]
+fn reference_graph_1() {
+    let doc = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'/>
+    <linearGradient id='lg2' xlink:href='#lg1'/>
+    <clipPath id='clip1'>
+        <rect fill='url(#lg2)'/>
+    </clipPath>
+    <g id='g1' clip-path='url(#clip1)'>
+        <rect id='rect1' fill='url(#lg1)'/>
+    </g>
+</svg>").unwrap();
+
+    let graph = doc.reference_graph();
+
+    let ids: Vec<(String, AId, String)> = graph.edges().iter()
+        .map(|r| (r.source.id().clone(), r.attribute, r.target.id().clone()))
+        .collect();
+    assert_eq!(ids, vec![
+        ("lg2".to_string(), AId::XlinkHref, "lg1".to_string()),
+        ("".to_string(), AId::Fill, "lg2".to_string()),
+        ("g1".to_string(), AId::ClipPath, "clip1".to_string()),
+        ("rect1".to_string(), AId::Fill, "lg1".to_string()),
+    ]);
+
+    let g = doc.descendants().find(|n| *n.id() == "g1").unwrap();
+    assert_eq!(graph.references(&g).len(), 1);
+
+    let mut deps: Vec<String> = graph.dependencies(&g).iter().map(|n| n.id().clone()).collect();
+    deps.sort();
+    assert_eq!(deps, vec!["clip1", "lg1", "lg2"]);
+
+    assert!(graph.cycles().is_empty());
+    assert!(graph.check_cycles().is_ok());
+}
+
+#[test]
+fn reference_graph_2() {
+    let doc = Document::from_str(
+"<svg>
+    <defs>
+        <linearGradient id='lg3' xlink:href='#lg2'/>
+        <pattern id='patt1'>
+            <rect fill='url(#lg3)'/>
+        </pattern>
+        <linearGradient id='lg2' xlink:href='#lg1'/>
+        <linearGradient id='lg1'/>
+    </defs>
+</svg>").unwrap();
+
+    let ids: Vec<String> = doc.reference_graph().defs_order().unwrap()
+        .iter().map(|n| n.id().clone()).collect();
+    assert_eq!(ids, vec!["lg1", "lg2", "lg3", "patt1"]);
+}
+
+#[test]
+fn reference_graph_cycle_1() {
+    let doc = Document::from_str(
+"<svg>
+    <g id='g1'>
+        <use id='use1' xlink:href='#g1'/>
+    </g>
+</svg>").unwrap();
+
+    let graph = doc.reference_graph();
+    assert_eq!(graph.cycles().len(), 1);
+    assert_eq!(graph.check_cycles().unwrap_err().full_chain(),
+               "Error: reference cycle: #g1 -> #use1 -> #g1");
+}
+
+#[test]
+fn reference_graph_cycle_2() {
+    let doc = Document::from_str(
+"<svg>
+    <defs>
+        <pattern id='patt1'>
+            <rect fill='url(#patt1)'/>
+        </pattern>
+    </defs>
+</svg>").unwrap();
+
+    let graph = doc.reference_graph();
+
+    let patt = doc.descendants().find(|n| *n.id() == "patt1").unwrap();
+    assert_eq!(graph.dependencies(&patt), vec![patt.clone()]);
+
+    assert_eq!(graph.check_cycles().unwrap_err().full_chain(),
+               "Error: reference cycle: #patt1 -> rect -> #patt1");
+    assert!(graph.defs_order().is_err());
+}
+
+#[test]
+fn reference_graph_cycle_3() {
+    // links from the 'a' and animation elements are not dependencies
+    let doc = Document::from_str(
+"<svg>
+    <g id='g1'>
+        <a xlink:href='#g1'/>
+        <rect id='rect1'>
+            <set xlink:href='#rect1' attributeName='fill' to='red'/>
+        </rect>
+    </g>
+</svg>").unwrap();
+
+    let graph = doc.reference_graph();
+    assert_eq!(graph.edges().len(), 2);
+    assert!(graph.cycles().is_empty());
+}
+
+fn observe(doc: &mut Document) -> Rc<RefCell<Vec<Mutation>>> {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let log2 = log.clone();
+    doc.add_observer(move |m: &Mutation| log2.borrow_mut().push(m.clone()));
+    log
+}
+
+#[test]
+fn observer_1() {
+    let mut doc = Document::from_str(
+"<svg>
+    <rect id='rect1' width='10'/>
+</svg>").unwrap();
+
+    let log = observe(&mut doc);
+
+    let svg = doc.svg_element().unwrap();
+    let mut rect = svg.first_child().unwrap();
+
+    rect.set_attribute((AId::Width, 20.0));
+    rect.set_attribute((AId::Height, 5.0));
+    rect.remove_attribute(AId::Width);
+    rect.remove_attribute(AId::Width);
+    rect.set_id("rect2");
+    // setting the same ID is not reported
+    rect.set_id("rect2");
+
+    let log = log.borrow();
+    assert_eq!(log.len(), 4);
+
+    match log[0] {
+        Mutation::Attribute { ref node, ref name, old_attribute: Some(ref old) } => {
+            assert_eq!(*node, rect);
+            assert_eq!(*name, Name::Id(AId::Width));
+            assert_eq!(old.value, AttributeValue::from(Length::new_number(10.0)));
+        }
+        _ => unreachable!(),
+    }
+
+    match log[1] {
+        Mutation::Attribute { ref name, old_attribute: None, .. } => {
+            assert_eq!(*name, Name::Id(AId::Height));
+        }
+        _ => unreachable!(),
+    }
+
+    match log[2] {
+        Mutation::Attribute { old_attribute: Some(ref old), .. } => {
+            assert_eq!(old.value, AttributeValue::from(20.0));
+        }
+        _ => unreachable!(),
+    }
+
+    match log[3] {
+        Mutation::Id { ref old_value, .. } => assert_eq!(old_value, "rect1"),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn observer_2() {
+    let mut doc = Document::from_str(
+"<svg>
+    <g/>
+    <circle/>
+    <rect/>
+</svg>").unwrap();
+
+    let log = observe(&mut doc);
+
+    let svg = doc.svg_element().unwrap();
+    let mut g = svg.first_child().unwrap();
+    let mut rect = svg.last_child().unwrap();
+
+    // move
+    g.append(&rect);
+    rect.detach();
+    // detaching of a detached node is not reported
+    rect.detach();
+    g.insert_before(&rect);
+
+    let log = log.borrow();
+    assert_eq!(log.len(), 3);
+
+    match log[0] {
+        Mutation::Insert { ref node, ref old_parent, ref old_next_sibling } => {
+            assert_eq!(*node, rect);
+            assert_eq!(*old_parent, Some(svg.clone()));
+            assert_eq!(*old_next_sibling, None);
+        }
+        _ => unreachable!(),
+    }
+
+    match log[1] {
+        Mutation::Detach { ref old_parent, .. } => assert_eq!(*old_parent, g),
+        _ => unreachable!(),
+    }
+
+    match log[2] {
+        Mutation::Insert { ref old_parent, .. } => assert_eq!(*old_parent, None),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn observer_3() {
+    let mut doc = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'/>
+    <rect fill='url(#lg1)'/>
+</svg>").unwrap();
+
+    let log = observe(&mut doc);
+
+    // links to the removed node are reported too
+    let mut lg = doc.svg_element().unwrap().first_child().unwrap();
+    lg.remove();
+
+    {
+        let log = log.borrow();
+        assert_eq!(log.len(), 2);
+        assert!(log[0].node().is_tag_name(EId::Rect));
+        match log[1] {
+            Mutation::Detach { ref node, .. } => assert_eq!(*node, lg),
+            _ => unreachable!(),
+        }
+    }
+
+    log.borrow_mut().clear();
+
+    let id = doc.add_observer(|_: &Mutation| {});
+    assert_eq!(doc.remove_observer(id), true);
+    assert_eq!(doc.remove_observer(id), false);
+
+    let mut text = doc.create_node(svgdom::NodeType::Text, "text");
+    text.set_text("new");
+    // setting the same text is not reported
+    text.set_text("new");
+    assert_eq!(log.borrow().len(), 1);
+    match log.borrow()[0] {
+        Mutation::Text { ref old_value, .. } => assert_eq!(old_value, "text"),
+        _ => unreachable!(),
+    };
+}
+
+#[test]
+fn move_sibling_1() {
+    let doc = Document::from_str("<svg><rect/><circle/><line/></svg>").unwrap();
+    let mut svg = doc.svg_element().unwrap();
+    let mut rect = svg.first_child().unwrap();
+    let mut line = svg.last_child().unwrap();
+
+    // The inserted node is detached from its old position first.
+    svg.append(&rect);
+    assert_eq!(doc.to_string(), "<svg>\n    <circle/>\n    <line/>\n    <rect/>\n</svg>\n");
+
+    svg.prepend(&line);
+    assert_eq!(doc.to_string(), "<svg>\n    <line/>\n    <circle/>\n    <rect/>\n</svg>\n");
+
+    rect.insert_before(&line);
+    assert_eq!(doc.to_string(), "<svg>\n    <circle/>\n    <line/>\n    <rect/>\n</svg>\n");
+
+    line.insert_after(&rect);
+    line.insert_before(&rect);
+    assert_eq!(doc.to_string(), "<svg>\n    <circle/>\n    <rect/>\n    <line/>\n</svg>\n");
+}
+
+#[test]
+fn history_1() {
+    let mut doc = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'/>
+    <g id='g1'>
+        <rect id='rect1' fill='url(#lg1)' width='10'/>
+        <text>Text</text>
+    </g>
+</svg>").unwrap();
+
+    let orig = doc.to_string();
+
+    let mut history = History::new(&mut doc);
+
+    history.begin();
+    {
+        let svg = doc.svg_element().unwrap();
+        let mut g = doc.descendants().find(|n| *n.id() == "g1").unwrap();
+        let mut rect = g.first_child().unwrap();
+        let mut lg = svg.first_child().unwrap();
+
+        rect.set_attribute((AId::Width, 20.0));
+        rect.set_id("rect2");
+        g.last_child().unwrap().first_child().unwrap().set_text("New");
+        g.insert_before(&rect);
+        lg.remove();
+        g.remove();
+    }
+    history.commit();
+
+    let changed = doc.to_string();
+    assert_eq_text!(changed,
+"<svg>
+    <rect id=\"rect2\" width=\"20\"/>
+</svg>
+");
+
+    assert_eq!(history.undo(), true);
+    assert_eq_text!(doc.to_string(), orig);
+    assert_eq!(history.undo(), false);
+
+    assert_eq!(history.redo(), true);
+    assert_eq_text!(doc.to_string(), changed);
+    assert_eq!(history.redo(), false);
+
+    assert_eq!(history.undo(), true);
+    assert_eq_text!(doc.to_string(), orig);
+
+    // links are restored too
+    let lg = doc.svg_element().unwrap().first_child().unwrap();
+    assert_eq!(lg.uses_count(), 1);
+}
+
+#[test]
+fn history_2() {
+    let mut doc = Document::from_str(
+"<svg>
+    <rect/>
+</svg>").unwrap();
+
+    let mut history = History::new(&mut doc);
+    let mut svg = doc.svg_element().unwrap();
+
+    history.begin();
+    svg.set_attribute((AId::Width, 10.0));
+    history.begin();
+    svg.set_attribute((AId::Height, 10.0));
+    history.rollback();
+    history.commit();
+
+    assert_eq_text!(doc.to_string(),
+"<svg width=\"10\">
+    <rect/>
+</svg>
+");
+
+    history.begin();
+    svg.first_child().unwrap().remove();
+    history.rollback();
+
+    assert_eq_text!(doc.to_string(),
+"<svg width=\"10\">
+    <rect/>
+</svg>
+");
+
+    // empty transactions are ignored
+    history.begin();
+    history.commit();
+
+    // setting the same ID is not a modification
+    history.begin();
+    svg.set_id("");
+    history.commit();
+
+    assert_eq!(history.undo(), true);
+    assert_eq!(history.can_undo(), false);
+    assert_eq!(history.can_redo(), true);
+
+    // modifications outside of a transaction clear the history
+    svg.set_attribute((AId::Height, 10.0));
+    assert_eq!(history.can_redo(), false);
+}
+
+#[test]
+fn diff_1() {
+    // formatting and attributes order are ignored
+    let old = Document::from_str(
+"<svg>
+    <rect x='1.0' y='2' fill='#ff0000' transform='translate(10)'/>
+    <path d='M 10 20 L 30 40'/>
+</svg>").unwrap();
+
+    let new = Document::from_str(
+"<svg>
+    <rect transform='matrix(1 0 0 1 10 0)' fill='red' y='2.000' x='1'/>
+    <path d='M10,20 30,40'/>
+</svg>").unwrap();
+
+    assert!(old.diff(&new).is_empty());
+}
+
+#[test]
+fn diff_2() {
+    let old = Document::from_str(
+"<svg>
+    <rect width='10'/>
+    <!--comment-->
+    <circle/>
+</svg>").unwrap();
+
+    let new = Document::from_str(
+"<svg>
+    <rect width='20' height='5'/>
+    <!--new comment-->
+    <ellipse/>
+</svg>").unwrap();
+
+    let changes = old.diff(&new);
+    assert_eq!(changes.len(), 5);
+
+    match changes[0] {
+        Change::Attribute { ref name, .. } => assert_eq!(*name, Name::Id(AId::Width)),
+        _ => unreachable!(),
+    }
+
+    match changes[1] {
+        Change::Attribute { ref name, .. } => assert_eq!(*name, Name::Id(AId::Height)),
+        _ => unreachable!(),
+    }
+
+    match changes[2] {
+        Change::Text { ref new, .. } => assert_eq!(*new.text(), "new comment"),
+        _ => unreachable!(),
+    }
+
+    match changes[3] {
+        Change::Removed(ref node) => assert!(node.is_tag_name(EId::Circle)),
+        _ => unreachable!(),
+    }
+
+    match changes[4] {
+        Change::Inserted(ref node) => assert!(node.is_tag_name(EId::Ellipse)),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn diff_3() {
+    // nodes are matched by id
+    let old = Document::from_str(
+"<svg>
+    <g id='g1'>
+        <rect id='rect1'/>
+    </g>
+    <g id='g2'/>
+    <circle id='circle1'/>
+    <ellipse id='ellipse1'/>
+</svg>").unwrap();
+
+    let new = Document::from_str(
+"<svg>
+    <g id='g1'/>
+    <g id='g2'>
+        <rect id='rect1'/>
+    </g>
+    <ellipse id='ellipse1'/>
+    <circle id='circle1'/>
+</svg>").unwrap();
+
+    let changes = old.diff(&new);
+    assert_eq!(changes.len(), 2);
+
+    match changes[0] {
+        Change::Moved { ref old, ref new } => {
+            assert_eq!(*old.id(), "rect1");
+            assert_eq!(*new.parent().unwrap().id(), "g2");
+        }
+        _ => unreachable!(),
+    }
+
+    match changes[1] {
+        Change::Moved { ref old, .. } => assert!(*old.id() == "circle1" || *old.id() == "ellipse1"),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn diff_4() {
+    let old = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'/>
+    <rect id='rect1' fill='url(#lg1)'/>
+</svg>").unwrap();
+
+    let new = Document::from_str(
+"<svg>
+    <linearGradient id='lg2'/>
+    <rect id='rect2' fill='url(#lg2)'/>
+</svg>").unwrap();
+
+    let changes = old.diff(&new);
+    assert_eq!(changes.len(), 3);
+
+    match changes[0] {
+        Change::Id { ref old, ref new } => {
+            assert_eq!(*old.id(), "lg1");
+            assert_eq!(*new.id(), "lg2");
+        }
+        _ => unreachable!(),
+    }
+
+    match changes[1] {
+        Change::Id { .. } => {}
+        _ => unreachable!(),
+    }
+
+    match changes[2] {
+        Change::Attribute { ref name, .. } => assert_eq!(*name, Name::Id(AId::Fill)),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn merge_1() {
+    let base = Document::from_str(
+"<svg>
+    <rect id='rect1' x='1' y='1'/>
+    <circle id='circle1' r='5'/>
+    <ellipse/>
+</svg>").unwrap();
+
+    let ours = Document::from_str(
+"<svg>
+    <rect id='rect1' x='2' y='1'/>
+    <circle id='circle1' r='10'/>
+    <ellipse/>
+    <path/>
+</svg>").unwrap();
+
+    let theirs = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'/>
+    <rect id='rect1' x='3' y='1.0' fill='url(#lg1)'/>
+    <circle id='circle1' r='10'/>
+</svg>").unwrap();
+
+    let (doc, conflicts) = base.merge(&ours, &theirs);
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+
+    assert_eq_text!(doc.to_string_with_opt(&opt),
+"<svg>
+    <linearGradient id='lg1'/>
+    <rect id='rect1' fill='url(#lg1)' x='2' y='1'/>
+    <circle id='circle1' r='10'/>
+    <path/>
+</svg>
+");
+
+    assert_eq!(conflicts.len(), 1);
+    match conflicts[0] {
+        Conflict::Attribute { ref ours, ref name, .. } => {
+            assert_eq!(*ours.id(), "rect1");
+            assert_eq!(*name, Name::Id(AId::X));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn merge_2() {
+    // removed and modified nodes
+    let base = Document::from_str(
+"<svg>
+    <g id='g1'>
+        <rect/>
+    </g>
+    <g id='g2'>
+        <rect/>
+    </g>
+</svg>").unwrap();
+
+    let ours = Document::from_str(
+"<svg>
+    <g id='g1'>
+        <rect width='10'/>
+    </g>
+</svg>").unwrap();
+
+    let theirs = Document::from_str(
+"<svg>
+    <g id='g2'>
+        <rect width='20'/>
+    </g>
+</svg>").unwrap();
+
+    let (doc, conflicts) = base.merge(&ours, &theirs);
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+
+    assert_eq_text!(doc.to_string_with_opt(&opt),
+"<svg>
+    <g id='g1'>
+        <rect width='10'/>
+    </g>
+</svg>
+");
+
+    assert_eq!(conflicts.len(), 2);
+    match conflicts[0] {
+        Conflict::Remove { ref base, ours: None, theirs: Some(_) } => {
+            assert!(base.is_tag_name(EId::Rect));
+        }
+        _ => unreachable!(),
+    }
+
+    match conflicts[1] {
+        Conflict::Remove { ref base, ours: Some(_), theirs: None } => {
+            assert_eq!(*base.id(), "g1");
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn merge_3() {
+    // moved nodes
+    let base = Document::from_str(
+"<svg>
+    <g id='g1'/>
+    <rect id='rect1'/>
+    <circle id='circle1'/>
+</svg>").unwrap();
+
+    let ours = Document::from_str(
+"<svg>
+    <g id='g1'/>
+    <circle id='circle1'/>
+    <rect id='rect1'/>
+</svg>").unwrap();
+
+    let theirs = Document::from_str(
+"<svg>
+    <g id='g1'>
+        <circle id='circle1'/>
+    </g>
+    <rect id='rect1'/>
+</svg>").unwrap();
+
+    let (doc, conflicts) = base.merge(&ours, &theirs);
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+
+    assert_eq_text!(doc.to_string_with_opt(&opt),
+"<svg>
+    <g id='g1'/>
+    <circle id='circle1'/>
+    <rect id='rect1'/>
+</svg>
+");
+
+    assert_eq!(conflicts.len(), 1);
+    match conflicts[0] {
+        Conflict::Move { ref base, .. } => assert_eq!(*base.id(), "circle1"),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn merge_4() {
+    // identical moves
+    let base = Document::from_str(
+"<svg>
+    <g id='g1'/>
+    <rect id='rect1'/>
+    <circle id='circle1'/>
+</svg>").unwrap();
+
+    let text =
+"<svg>
+    <g id='g1'>
+        <circle id='circle1'/>
+    </g>
+    <rect id='rect1'/>
+</svg>";
+
+    let ours = Document::from_str(text).unwrap();
+    let theirs = Document::from_str(text).unwrap();
+
+    let (doc, conflicts) = base.merge(&ours, &theirs);
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+
+    assert_eq_text!(doc.to_string_with_opt(&opt),
+"<svg>
+    <g id='g1'>
+        <circle id='circle1'/>
+    </g>
+    <rect id='rect1'/>
+</svg>
+");
+
+    assert!(conflicts.is_empty());
+}
+
+#[test]
+fn user_data_1() {
+    #[derive(Clone, PartialEq, Debug)]
+    struct BBox(f64, f64);
+
+    let doc = Document::from_str("<svg><rect/></svg>").unwrap();
+    let mut rect = doc.svg_element().unwrap().first_child().unwrap();
+
+    rect.set_user_data(BBox(1.0, 2.0));
+    rect.set_user_data(5u32);
+    rect.set_user_data(7u32);
+
+    rect.user_data_mut::<BBox>().unwrap().0 = 3.0;
+    assert_eq!(*rect.user_data::<BBox>().unwrap(), BBox(3.0, 2.0));
+    assert_eq!(*rect.user_data::<u32>().unwrap(), 7);
+
+    // user data is not copied by default
+    let mut copy = rect.make_copy();
+    assert!(copy.user_data::<u32>().is_none());
+
+    copy.set_user_data(1u32);
+    copy.copy_user_data::<u32>(&rect);
+    copy.copy_user_data::<BBox>(&rect);
+    copy.copy_user_data::<f64>(&rect);
+    assert_eq!(*copy.user_data::<u32>().unwrap(), 7);
+    assert_eq!(*copy.user_data::<BBox>().unwrap(), BBox(3.0, 2.0));
+    assert!(copy.user_data::<f64>().is_none());
+
+    // copies are independent
+    copy.user_data_mut::<BBox>().unwrap().0 = 4.0;
+    assert_eq!(rect.user_data::<BBox>().unwrap().0, 3.0);
+
+    assert_eq!(rect.remove_user_data::<u32>(), Some(7));
+    assert_eq!(rect.remove_user_data::<u32>(), None);
+
+    assert_eq!(doc.to_string(), "<svg>\n    <rect/>\n</svg>\n");
+}
+
+#[test]
+fn user_data_2() {
+    use std::rc::Rc;
+
+    // A non-cloneable handle.
+    struct Handle(Rc<u32>);
+
+    let mut doc = Document::new();
+    let mut rect = doc.create_element(EId::Rect);
+    let rc = Rc::new(5);
+    rect.set_user_data(Handle(rc.clone()));
+    assert_eq!(*rect.user_data::<Handle>().unwrap().0, 5);
+    assert_eq!(Rc::strong_count(&rc), 2);
+
+    rect.remove();
+    drop(rect);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn validate_1() {
+    let doc = Document::from_str(
+"<svg>
+    <rect width='10' height='10' opacity='0.5'/>
+    <circle r='5'/>
+    <linearGradient>
+        <stop offset='50%'/>
+    </linearGradient>
+    <text>Text</text>
+</svg>").unwrap();
+
+    assert!(doc.validate().is_empty());
+}
+
+#[test]
+fn validate_2() {
+    let doc = Document::from_str(
+"<svg>
+    <rect width='10' height='-10'/>
+    <circle r='5'><rect width='1' height='1'/></circle>
+    <ellipse rx='1'/>
+    <g>text</g>
+    <set fill='freeze' stroke='red'/>
+    <path d='M 10 20 L 30 40' cx='5'/>
+    <stop offset='150%'/>
+    <hatch/>
+</svg>").unwrap();
+
+    // the parser clamps the opacity, so set it directly
+    let mut path = doc.descendants().find(|n| n.is_tag_name(EId::Path)).unwrap();
+    path.set_attribute((AId::Opacity, 2.0));
+
+    let list: Vec<_> = doc.validate().into_iter()
+        .map(|v| (v.node.tag_id(), v.kind))
+        .collect();
+
+    assert_eq!(list, vec![
+        (Some(EId::Rect), ViolationKind::InvalidValue(AId::Height)),
+        (Some(EId::Rect), ViolationKind::InvalidChild),
+        (Some(EId::Ellipse), ViolationKind::MissingAttribute(AId::Ry)),
+        (None, ViolationKind::InvalidChild),
+        (Some(EId::Set), ViolationKind::InvalidAttribute(AId::Stroke)),
+        (Some(EId::Path), ViolationKind::InvalidAttribute(AId::Cx)),
+        (Some(EId::Path), ViolationKind::InvalidValue(AId::Opacity)),
+        (Some(EId::Stop), ViolationKind::InvalidChild),
+        (Some(EId::Stop), ViolationKind::InvalidValue(AId::Offset)),
+        // SVG 2 elements are not allowed by the SVG 1.1 content model
+        (Some(EId::Hatch), ViolationKind::InvalidChild),
+    ]);
+}
+
+#[test]
+fn view_box_transform_1() {
+    let doc = Document::from_str(
+"<svg width='100' height='100' viewBox='0 0 50 100' preserveAspectRatio='xMaxYMid slice'>
+    <marker viewBox='10 10 6 6'/>
+    <pattern width='10%' height='10' viewBox='0 0 1 1'/>
+    <image x='10' y='20' width='40' height='20'/>
+</svg>").unwrap();
+
+    let svg = doc.svg_element().unwrap();
+    let marker = svg.children().nth(0).unwrap();
+    let pattern = svg.children().nth(1).unwrap();
+    let image = svg.children().nth(2).unwrap();
+
+    assert_eq!(svg.attributes().get_value(AId::ViewBox).unwrap(),
+               &AttributeValue::ViewBox(Rect::new(0.0, 0.0, 50.0, 100.0)));
+
+    assert!(svg.view_box_transform().unwrap()
+               .fuzzy_eq(&Transform::new(2.0, 0.0, 0.0, 2.0, 0.0, -50.0)));
+
+    // 'markerWidth' and 'markerHeight' are 3 by default
+    assert!(marker.view_box_transform().unwrap()
+                  .fuzzy_eq(&Transform::new(0.5, 0.0, 0.0, 0.5, -5.0, -5.0)));
+
+    // percentages are not resolved
+    assert_eq!(pattern.view_box_transform(), None);
+    assert_eq!(image.view_box_transform(), None);
+
+    assert!(image.image_transform(100.0, 100.0).unwrap()
+                 .fuzzy_eq(&Transform::new(0.2, 0.0, 0.0, 0.2, 20.0, 20.0)));
+    assert_eq!(image.image_transform(0.0, 100.0), None);
+}
+
+#[test]
+fn view_box_transform_2() {
+    // a zero-size viewBox disables rendering
+    let doc = Document::from_str("<svg width='100' height='100' viewBox='0 0 0 100'/>").unwrap();
+    let svg = doc.svg_element().unwrap();
+    assert_eq!(svg.view_box_transform(), None);
+}
+
+#[test]
+fn current_color_1() {
+    let doc = Document::from_str(
+"<svg color='#ff0000 icc-color(p, 1)'>
+    <g color='inherit'>
+        <rect fill='currentColor'/>
+    </g>
+    <g color='blue'/>
+</svg>").unwrap();
+
+    let mut iter = doc.descendants().filter(|n| n.is_svg_element());
+    let svg = iter.next().unwrap();
+    let g1 = iter.next().unwrap();
+    let rect = iter.next().unwrap();
+    let g2 = iter.next().unwrap();
+
+    assert_eq!(rect.attributes().get_value(AId::Fill), Some(&AttributeValue::CurrentColor));
+    assert_eq!(svg.current_color(), Some(Color::new(255, 0, 0)));
+    assert_eq!(g1.current_color(), Some(Color::new(255, 0, 0)));
+    assert_eq!(rect.current_color(), Some(Color::new(255, 0, 0)));
+    assert_eq!(g2.current_color(), Some(Color::new(0, 0, 255)));
+    assert_eq!(Document::new().create_element(EId::Rect).current_color(), None);
+}
+
+#[test]
+fn conversion_context_1() {
+    let doc = Document::from_str(
+"<svg width='400' height='200' font-size='20'>
+    <svg width='50%' height='50%' font-size='larger'>
+        <g font-size='150%'>
+            <rect x='10%' y='10%' width='2em' height='1in' font-size='2em' stroke-width='10%'/>
+        </g>
+    </svg>
+</svg>").unwrap();
+
+    let rect = doc.descendants().find(|n| n.is_tag_name(EId::Rect)).unwrap();
+
+    let convert = |id: AId| {
+        let ctx = rect.conversion_context(id);
+        match rect.attributes().get_value(id) {
+            Some(&AttributeValue::Length(l)) => l.to_user_units(&ctx),
+            _ => unreachable!(),
+        }
+    };
+
+    assert_eq!(convert(AId::X), 20.0);
+    assert_eq!(convert(AId::Y), 10.0);
+    assert_eq!(convert(AId::Width), 144.0);
+    assert_eq!(convert(AId::Height), 96.0);
+    assert_eq!(convert(AId::FontSize), 72.0);
+    assert_eq!(convert(AId::StrokeWidth), 25000f64.sqrt() / 10.0);
+
+    // a percentage font size is relative to the parent font size
+    let g = doc.descendants().find(|n| n.is_tag_name(EId::G)).unwrap();
+    let ctx = g.conversion_context(AId::FontSize);
+    assert_eq!(Length::new(150.0, LengthUnit::Percent).to_user_units(&ctx), 36.0);
+}
+
+#[test]
+fn bbox_1() {
+    let doc = Document::from_str(
+"<svg viewBox='0 0 200 100'>
+    <rect id='rect1' x='10%' y='10' width='50%' height='20' rx='5'/>
+    <circle id='circle1' cx='50' cy='50' r='10'/>
+    <ellipse id='ellipse1' cx='50' cy='50' rx='20' ry='10'/>
+    <line id='line1' x1='10' y1='20' x2='-10' y2='40'/>
+    <polygon id='polygon1' points='10 10 30 50 -5 20'/>
+    <path id='path1' d='M 0 0 Q 5 10 10 0'/>
+    <rect id='rect2' width='0' height='20'/>
+    <text id='text1'>Text</text>
+    <defs id='defs1'/>
+</svg>").unwrap();
+
+    let bbox = |id: &str| doc.descendants().find(|n| *n.id() == id).unwrap().bbox();
+
+    assert_eq!(bbox("rect1"), Some(Rect::new(20.0, 10.0, 100.0, 20.0)));
+    assert_eq!(bbox("circle1"), Some(Rect::new(40.0, 40.0, 20.0, 20.0)));
+    assert_eq!(bbox("ellipse1"), Some(Rect::new(30.0, 40.0, 40.0, 20.0)));
+    assert_eq!(bbox("line1"), Some(Rect::new(-10.0, 20.0, 20.0, 20.0)));
+    assert_eq!(bbox("polygon1"), Some(Rect::new(-5.0, 10.0, 35.0, 40.0)));
+    assert_eq!(bbox("path1"), Some(Rect::new(0.0, 0.0, 10.0, 5.0)));
+    assert_eq!(bbox("rect2"), None);
+    assert_eq!(bbox("text1"), None);
+    assert_eq!(bbox("defs1"), None);
+}
+
+#[test]
+fn bbox_2() {
+    let doc = Document::from_str(
+"<svg xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <defs>
+        <rect id='rect1' width='10' height='20' transform='translate(5 0)'/>
+    </defs>
+    <g id='g1' transform='translate(100 100)'>
+        <g transform='scale(2)'>
+            <use xlink:href='#rect1' x='10' y='10'/>
+        </g>
+        <line x1='0' y1='0' x2='10' y2='0' transform='rotate(90)'/>
+    </g>
+    <use id='use1' xlink:href='#g1' transform='translate(1000)'/>
+</svg>").unwrap();
+
+    let bbox = |id: &str| doc.descendants().find(|n| *n.id() == id).unwrap().bbox().unwrap();
+
+    assert!(bbox("g1").fuzzy_eq(&Rect::new(0.0, 0.0, 50.0, 60.0)));
+    assert!(bbox("use1").fuzzy_eq(&Rect::new(100.0, 100.0, 50.0, 60.0)));
+    let svg = doc.svg_element().unwrap();
+    assert!(svg.bbox().unwrap().fuzzy_eq(&Rect::new(100.0, 100.0, 1050.0, 60.0)));
+}
+
+#[test]
+fn bbox_3() {
+    let doc = Document::from_str(
+"<svg>
+    <g id='g1' stroke='black' stroke-width='4'>
+        <rect width='10' height='20'/>
+        <rect x='20' width='10' height='20' stroke-width='10' transform='scale(2 1)'/>
+        <rect x='100' width='10' height='20' stroke='none'/>
+    </g>
+    <rect id='rect1' width='10' height='20'/>
+    <rect id='rect2' width='10' height='20' stroke='red'/>
+</svg>").unwrap();
+
+    let node = |id: &str| doc.descendants().find(|n| *n.id() == id).unwrap();
+
+    assert_eq!(node("g1").bbox(), Some(Rect::new(0.0, 0.0, 110.0, 20.0)));
+    assert_eq!(node("g1").bbox_with_stroke(), Some(Rect::new(-2.0, -5.0, 112.0, 30.0)));
+    assert_eq!(node("rect1").bbox_with_stroke(), Some(Rect::new(0.0, 0.0, 10.0, 20.0)));
+    assert_eq!(node("rect2").bbox_with_stroke(), Some(Rect::new(-0.5, -0.5, 11.0, 21.0)));
+}
+
+#[test]
+fn bbox_4() {
+    // cyclic references
+    let doc = Document::from_str(
+"<svg xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <g id='a'>
+        <rect width='10' height='10'/>
+        <use xlink:href='#b'/>
+        <use xlink:href='#b'/>
+    </g>
+    <g id='b'>
+        <use xlink:href='#a'/>
+        <use xlink:href='#a'/>
+    </g>
+    <rect id='rect1' width='10' height='10'/>
+    <use id='use1' xlink:href='#rect1'/>
+    <use id='use2' xlink:href='#rect1'/>
+</svg>").unwrap();
+
+    let node = |id: &str| doc.descendants().find(|n| *n.id() == id).unwrap();
+
+    assert_eq!(node("a").bbox(), None);
+    assert_eq!(node("b").bbox_with_stroke(), None);
+    assert_eq!(doc.svg_element().unwrap().bbox(), None);
+    assert_eq!(node("use1").bbox(), Some(Rect::new(0.0, 0.0, 10.0, 10.0)));
+}
+
+#[test]
+fn bbox_5() {
+    let doc = Document::from_str(
+"<svg xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <g id='g1'>
+        <svg x='100' y='100' width='10' height='10' viewBox='0 0 100 100'>
+            <rect width='100' height='100'/>
+        </svg>
+    </g>
+    <g id='g2'>
+        <rect width='10' height='10'/>
+        <symbol id='symbol1'>
+            <rect x='50' y='50' width='10' height='10'/>
+        </symbol>
+    </g>
+    <use id='use1' xlink:href='#symbol1'/>
+</svg>").unwrap();
+
+    let node = |id: &str| doc.descendants().find(|n| *n.id() == id).unwrap();
+
+    assert!(node("g1").bbox().unwrap().fuzzy_eq(&Rect::new(100.0, 100.0, 10.0, 10.0)));
+    assert_eq!(node("g2").bbox(), Some(Rect::new(0.0, 0.0, 10.0, 10.0)));
+    assert_eq!(node("use1").bbox(), Some(Rect::new(50.0, 50.0, 10.0, 10.0)));
+}
+
+#[test]
+fn bbox_6() {
+    let doc = Document::from_str(
+"<svg xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <symbol id='symbol1' viewBox='0 0 10 10'>
+        <rect width='10' height='10'/>
+    </symbol>
+    <svg id='svg1' x='5' width='100' height='100' viewBox='0 0 10 10'>
+        <rect width='10' height='10'/>
+    </svg>
+    <use id='use1' xlink:href='#symbol1' width='100' height='100'/>
+    <use id='use2' xlink:href='#svg1'/>
+    <switch id='switch1'>
+        <rect width='10' height='10'/>
+        <rect width='20' height='20'/>
+    </switch>
+</svg>").unwrap();
+
+    let node = |id: &str| doc.descendants().find(|n| *n.id() == id).unwrap();
+
+    assert!(node("use1").bbox().unwrap().fuzzy_eq(&Rect::new(0.0, 0.0, 100.0, 100.0)));
+    assert!(node("use2").bbox().unwrap().fuzzy_eq(&Rect::new(5.0, 0.0, 100.0, 100.0)));
+    assert_eq!(node("switch1").bbox(), Some(Rect::new(0.0, 0.0, 10.0, 10.0)));
+}
+
+#[test]
+fn shape_to_path_1() {
+    let doc = Document::from_str(
+"<svg viewBox='0 0 200 100'>
+    <rect id='rect1' x='10' y='10' width='50%' height='20' rx='5'/>
+    <rect id='rect2' width='10' height='20' rx='10' ry='2'/>
+    <rect id='rect3' width='1in' height='10' ry='-1'/>
+    <circle id='circle1' cx='50' cy='50' r='10'/>
+    <ellipse id='ellipse1' cx='50' cy='50' rx='20' ry='10'/>
+    <line id='line1' x1='10' y1='20' x2='-10' y2='40'/>
+    <polyline id='polyline1' points='10 10 30 50 -5 20'/>
+    <polygon id='polygon1' points='10 10 30 50 -5 20'/>
+    <polygon id='polygon2' points='10 10'/>
+    <circle id='circle2' r='0'/>
+    <g id='g1'/>
+</svg>").unwrap();
+
+    let path = |id: &str| {
+        doc.descendants().find(|n| *n.id() == id).unwrap().shape_to_path().map(|p| p.to_string())
+    };
+
+    assert_eq!(path("rect1").unwrap(),
+               "M 15 10 H 105 A 5 5 0 0 1 110 15 V 25 A 5 5 0 0 1 105 30 \
+                H 15 A 5 5 0 0 1 10 25 V 15 A 5 5 0 0 1 15 10 Z");
+    assert_eq!(path("rect2").unwrap(),
+               "M 5 0 H 5 A 5 2 0 0 1 10 2 V 18 A 5 2 0 0 1 5 20 \
+                H 5 A 5 2 0 0 1 0 18 V 2 A 5 2 0 0 1 5 0 Z");
+    assert_eq!(path("rect3").unwrap(), "M 0 0 H 96 V 10 H 0 Z");
+    assert_eq!(path("circle1").unwrap(),
+               "M 60 50 A 10 10 0 0 1 50 60 A 10 10 0 0 1 40 50 \
+                A 10 10 0 0 1 50 40 A 10 10 0 0 1 60 50 Z");
+    assert_eq!(path("ellipse1").unwrap(),
+               "M 70 50 A 20 10 0 0 1 50 60 A 20 10 0 0 1 30 50 \
+                A 20 10 0 0 1 50 40 A 20 10 0 0 1 70 50 Z");
+    assert_eq!(path("line1").unwrap(), "M 10 20 L -10 40");
+    assert_eq!(path("polyline1").unwrap(), "M 10 10 L 30 50 L -5 20");
+    assert_eq!(path("polygon1").unwrap(), "M 10 10 L 30 50 L -5 20 Z");
+    assert_eq!(path("polygon2"), None);
+    assert_eq!(path("circle2"), None);
+    assert_eq!(path("g1"), None);
+}
+
+#[test]
+fn convert_shape_to_path_1() {
+    let doc = Document::from_str(
+"<svg xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <circle id='circle1' cx='10' cy='10' r='5' fill='red' transform='scale(2)'/>
+    <rect width='0' height='10'/>
+    <g/>
+    <use xlink:href='#circle1'/>
+</svg>").unwrap();
+
+    for mut node in doc.descendants() {
+        let is_circle = node.is_tag_name(EId::Circle);
+        assert_eq!(node.convert_shape_to_path(), is_circle);
+    }
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+    assert_eq_text!(doc.to_string_with_opt(&opt),
+"<svg xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <path id='circle1' d='M 15 10 A 5 5 0 0 1 10 15 A 5 5 0 0 1 5 10 A 5 5 0 0 1 10 5 \
+A 5 5 0 0 1 15 10 Z' fill='#ff0000' transform='matrix(2 0 0 2 0 0)'/>
+    <rect height='10' width='0'/>
+    <g/>
+    <use xlink:href='#circle1'/>
+</svg>
+");
+}
+
+#[test]
+fn bake_transforms_1() {
+    let mut doc = Document::from_str(
+"<svg>
+    <linearGradient id='lg1'/>
+    <clipPath id='clip1'/>
+    <g transform='translate(10 20)'>
+        <title>Title</title>
+        <g transform='scale(2)'>
+            <path d='M 0 0 h 10 v 10'/>
+            <path d='M 0 0 L 10 10' transform='rotate(90)'/>
+            <path d='M 0 0 L 10 10' stroke='black'/>
+            <path d='M 0 0 L 10 10' fill='url(#lg1)'/>
+            <rect width='10' height='10'/>
+        </g>
+    </g>
+    <g transform='translate(10 20)' clip-path='url(#clip1)'>
+        <path d='M 0 0 L 10 10'/>
+    </g>
+    <g transform='translate(10 20)' stroke='black'>
+        <path d='M 0 0 L 10 10'/>
+    </g>
+</svg>").unwrap();
+
+    doc.bake_transforms();
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+    assert_eq_text!(doc.to_string_with_opt(&opt),
+"<svg>
+    <linearGradient id='lg1'/>
+    <clipPath id='clip1'/>
+    <g>
+        <title>Title</title>
+        <g>
+            <path d='M 10 20 h 20 v 20'/>
+            <path d='M 10 20 L -10 40'/>
+            <path d='M 0 0 L 10 10' stroke='#000000' transform='matrix(2 0 0 2 10 20)'/>
+            <path d='M 0 0 L 10 10' fill='url(#lg1)' transform='matrix(2 0 0 2 10 20)'/>
+            <rect height='10' transform='matrix(2 0 0 2 10 20)' width='10'/>
+        </g>
+    </g>
+    <g clip-path='url(#clip1)' transform='matrix(1 0 0 1 10 20)'>
+        <path d='M 0 0 L 10 10'/>
+    </g>
+    <g stroke='#000000'>
+        <path d='M 10 20 L 20 30'/>
+    </g>
+</svg>
+");
+}
+
+#[test]
+fn bake_transforms_2() {
+    // A nested group without a transform.
+    let mut doc = Document::from_str(
+"<svg>
+    <g transform='translate(10 20)'>
+        <g>
+            <path d='M 0 0 L 10 10'/>
+        </g>
+    </g>
+</svg>").unwrap();
+
+    doc.bake_transforms();
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+    assert_eq_text!(doc.to_string_with_opt(&opt),
+"<svg>
+    <g>
+        <g>
+            <path d='M 10 20 L 20 30'/>
+        </g>
+    </g>
+</svg>
+");
+}
@@ -1007,3 +1007,177 @@ fn text_content_3() {
     assert_eq!(text, "Not all characters in the text have a specified rotation");
 }
 
+
+#[test]
+fn parse_svg2_1() {
+    let doc = Document::from_str(
+"<svg>
+    <solidcolor id='sc1' solid-color='red' solid-opacity='0.5'/>
+    <rect paint-order='stroke' vector-effect='non-scaling-stroke'
+          style='mix-blend-mode:multiply;isolation:isolate'/>
+    <use href='#sc1'/>
+    <mesh/>
+</svg>").unwrap();
+
+    let svg = doc.svg_element().unwrap();
+    let sc = svg.children().nth(0).unwrap();
+    let rect = svg.children().nth(1).unwrap();
+    let use_elem = svg.children().nth(2).unwrap();
+    let mesh = svg.children().nth(3).unwrap();
+
+    assert_eq!(sc.tag_id(), Some(EId::SolidColor));
+    assert_eq!(mesh.tag_id(), Some(EId::Mesh));
+    assert_eq!(sc.attributes().get_value(AId::SolidColor).unwrap(),
+               &AttributeValue::Color(Color::new(255, 0, 0)));
+    assert_eq!(sc.attributes().get_value(AId::SolidOpacity).unwrap(),
+               &AttributeValue::Number(0.5));
+
+    {
+        let attrs = rect.attributes();
+        assert_eq!(attrs.get_value(AId::VectorEffect).unwrap(),
+                   &AttributeValue::PredefValue(ValueId::NonScalingStroke));
+        assert_eq!(attrs.get_value(AId::MixBlendMode).unwrap(),
+                   &AttributeValue::PredefValue(ValueId::Multiply));
+        assert_eq!(attrs.get_value(AId::Isolation).unwrap(),
+                   &AttributeValue::PredefValue(ValueId::Isolate));
+    }
+
+    assert_eq!(sc.is_used(), true);
+    assert_eq!(use_elem.attributes().get_value(AId::Href).unwrap(), &AttributeValue::Link(sc));
+
+    assert_eq_text!(doc.to_string_with_opt(&write_options()),
+"<svg>
+    <solidcolor id='sc1' solid-color='#ff0000' solid-opacity='0.5'/>
+    <rect isolation='isolate' mix-blend-mode='multiply' paint-order='stroke' \
+vector-effect='non-scaling-stroke'/>
+    <use href='#sc1'/>
+    <mesh/>
+</svg>
+");
+}
+
+#[test]
+fn parse_svg2_2() {
+    let res = Document::from_str("<svg><rect vector-effect='fill'/></svg>");
+    assert_eq!(res.err().unwrap().full_chain(),
+        "Error: invalid attribute value at 1:27");
+}
+
+test_resave!(parse_view_box_1,
+"<svg viewBox='0.0,10 100.5 50' preserveAspectRatio=' xMinYMin   meet '>
+    <image preserveAspectRatio='defer none'/>
+</svg>",
+"<svg preserveAspectRatio='xMinYMin' viewBox='0 10 100.5 50'>
+    <image preserveAspectRatio='defer none'/>
+</svg>
+");
+
+#[test]
+fn parse_view_box_2() {
+    let res = Document::from_str("<svg viewBox='0 0 -10 10'/>");
+    assert_eq!(res.err().unwrap().full_chain(),
+        "Error: invalid attribute value at 1:15");
+}
+
+test_resave!(parse_points_1,
+"<svg>
+    <polyline points='10,20 30,40 50'/>
+    <polygon points=''/>
+</svg>",
+"<svg>
+    <polyline points='10 20 30 40'/>
+    <polygon/>
+</svg>
+");
+
+#[test]
+fn parse_points_2() {
+    let doc = Document::from_str("<svg><polygon points='10 20 30 40'/></svg>").unwrap();
+    let node = doc.root().descendants().find(|n| n.is_tag_name(EId::Polygon)).unwrap();
+    let attrs = node.attributes();
+    match attrs.get_value(AId::Points) {
+        Some(&AttributeValue::Points(ref points)) => {
+            assert_eq!(points.to_path(true).to_string(), "M 10 20 L 30 40 Z");
+        }
+        _ => unreachable!(),
+    }
+}
+
+test_resave!(parse_angle_1,
+"<svg>
+    <marker orient='0.5turn'/>
+    <marker orient='auto-start-reverse'/>
+    <text rotate='10, 20deg 1.5rad' glyph-orientation-vertical='90'>Text</text>
+    <animateMotion rotate='auto-reverse'/>
+</svg>",
+"<svg>
+    <marker orient='0.5turn'/>
+    <marker orient='auto-start-reverse'/>
+    <text glyph-orientation-vertical='90' rotate='10 20deg 1.5rad'>Text</text>
+    <animateMotion rotate='auto-reverse'/>
+</svg>
+");
+
+#[test]
+fn parse_angle_2() {
+    let res = Document::from_str("<svg><marker orient='10px'/></svg>");
+    assert_eq!(res.err().unwrap().full_chain(),
+        "Error: invalid attribute value at 1:22");
+}
+
+test_resave!(parse_color_1,
+"<svg>
+    <rect fill='rgba(255, 0, 0, 0.5)' stroke='transparent'/>
+    <stop stop-color='hsl(120, 100%, 25%)' style='flood-color:#f008'/>
+</svg>",
+"<svg>
+    <rect fill='#ff000080' stroke='#00000000'/>
+    <stop flood-color='#ff000088' stop-color='#008000'/>
+</svg>
+");
+
+test_resave!(parse_current_color_1,
+"<svg>
+    <rect fill='currentColor' stroke='url(#lg1) currentColor'/>
+    <solidcolor solid-color='currentColor'/>
+</svg>",
+"<svg>
+    <rect fill='currentColor' stroke='currentColor'/>
+    <solidcolor solid-color='currentColor'/>
+</svg>
+");
+
+test_resave!(parse_icc_color_1,
+"<svg>
+    <rect fill='#CD853F icc-color(acmecmyk, 0.11, 0.48, 0.83, 0.00)'/>
+    <stop style='stop-color:red icc-color(p, 1)'/>
+</svg>",
+"<svg>
+    <rect fill='#cd853f icc-color(acmecmyk 0.11 0.48 0.83 0)'/>
+    <stop stop-color='#ff0000 icc-color(p 1)'/>
+</svg>
+");
+
+#[test]
+fn parse_transform_lists_1() {
+    let mut opt = ParseOptions::default();
+    opt.parse_transform_lists = true;
+    let doc = Document::from_str_with_opt(
+"<svg>
+    <g transform='translate(10) rotate(45 5 5) scale(2)'/>
+    <linearGradient gradientTransform=' '/>
+</svg>", &opt).unwrap();
+    assert_eq_text!(doc.to_string_with_opt(&write_options()),
+"<svg>
+    <g transform='translate(10) rotate(45 5 5) scale(2)'/>
+    <linearGradient/>
+</svg>
+");
+}
+
+#[test]
+fn parse_transform_lists_2() {
+    let doc = Document::from_str("<svg transform='translate(10) scale(2)'/>").unwrap();
+    assert_eq_text!(doc.to_string_with_opt(&write_options()),
+                    "<svg transform='matrix(2 0 0 2 10 0)'/>\n");
+}
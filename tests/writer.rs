@@ -809,3 +809,23 @@ fn attrs_order_2() {
 "
 );
 }
+
+#[test]
+fn colors_1() {
+    let doc = Document::from_str(
+"<svg>
+    <rect fill='#ff0000' stroke='rgba(255, 255, 255, 0.5)' stop-color='#d2b48c'/>
+</svg>"
+).unwrap();
+
+    let mut opt = WriteOptions::default();
+    opt.use_single_quote = true;
+    opt.trim_hex_colors = true;
+    opt.use_color_keywords = true;
+    assert_eq_text!(doc.to_string_with_opt(&opt),
+"<svg>
+    <rect fill='red' stop-color='tan' stroke='#ffffff80'/>
+</svg>
+"
+);
+}